@@ -0,0 +1,41 @@
+use std::time::Instant;
+
+use servidor_http::request::Method;
+use servidor_http::router::{Route, RouteTable};
+
+const ITERATIONS: u32 = 100_000;
+
+fn main() {
+    let mut table = RouteTable::new();
+
+    for index in 0..100 {
+        table
+            .insert(Route::new(Method::GET, &format!("/resource{}/:id", index)))
+            .unwrap();
+    }
+
+    table
+        .insert(Route::new(Method::GET, "/static/*path"))
+        .unwrap();
+
+    let routes = [
+        Route::new(Method::GET, "/resource0/1"),
+        Route::new(Method::GET, "/resource99/42"),
+        Route::new(Method::GET, "/static/css/main.css"),
+        Route::new(Method::GET, "/missing"),
+    ];
+
+    for route in &routes {
+        let start = Instant::now();
+
+        for _ in 0..ITERATIONS {
+            let _ = std::hint::black_box(table.resolve(std::hint::black_box(route)));
+        }
+
+        println!(
+            "resolve {:<24} {:>8.0} ns/iter",
+            route.path,
+            start.elapsed().as_nanos() as f64 / f64::from(ITERATIONS)
+        );
+    }
+}
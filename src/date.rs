@@ -0,0 +1,122 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Formats a [SystemTime] as an HTTP-date (IMF-fixdate), like `Sun, 06 Nov 1994 08:49:37 GMT`. Times before the unix epoch are formatted as the epoch.
+pub fn format_http_date(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    let days = seconds / 86400;
+    let seconds_of_day = seconds % 86400;
+
+    let (year, month, day) = civil_from_days(days as i64);
+
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        WEEKDAYS[(days % 7) as usize],
+        day,
+        MONTHS[(month - 1) as usize],
+        year,
+        seconds_of_day / 3600,
+        (seconds_of_day % 3600) / 60,
+        seconds_of_day % 60
+    )
+}
+
+/// Parses an HTTP-date in the IMF-fixdate format (`Sun, 06 Nov 1994 08:49:37 GMT`) or the legacy asctime format (`Sun Nov  6 08:49:37 1994`). Returns None if the date is malformed (Including years without exactly 4 digits) or before the unix epoch.
+pub fn parse_http_date(date: &str) -> Option<SystemTime> {
+    let parts: Vec<&str> = date.split_whitespace().collect();
+
+    let (day, month, year, time) = match parts.as_slice() {
+        [weekday, day, month, year, time, "GMT"] if weekday.ends_with(',') => {
+            (*day, *month, *year, *time)
+        }
+        [_, month, day, time, year] => (*day, *month, *year, *time),
+        _ => return None,
+    };
+
+    let day: u32 = day.parse().ok()?;
+    let month = MONTHS.iter().position(|name| *name == month)? as u32 + 1;
+    // Years are limited to 4 digits, so a client can't overflow the arithmetic with a huge year
+    if year.len() != 4 || !year.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let year: i64 = year.parse().ok()?;
+
+    let mut time_parts = time.split(':').map(|part| part.parse::<u64>().ok());
+    let (hours, minutes, seconds) = match (
+        time_parts.next().flatten(),
+        time_parts.next().flatten(),
+        time_parts.next().flatten(),
+        time_parts.next(),
+    ) {
+        (Some(hours), Some(minutes), Some(seconds), None)
+            if hours < 24 && minutes < 60 && seconds < 61 =>
+        {
+            (hours, minutes, seconds)
+        }
+        _ => return None,
+    };
+
+    if !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+
+    let days = u64::try_from(days_from_civil(year, month, day)?).ok()?;
+    let seconds = days
+        .checked_mul(86400)?
+        .checked_add(hours * 3600 + minutes * 60 + seconds)?;
+
+    UNIX_EPOCH.checked_add(Duration::from_secs(seconds))
+}
+
+// Leap years are the years divisible by 4, except the centuries not divisible by 400
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Inverse of civil_from_days, returns None if the days overflow
+fn days_from_civil(year: i64, month: u32, day: u32) -> Option<i64> {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_index = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let day_of_year = (153 * month_index + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era.checked_mul(146097)?
+        .checked_add(day_of_era)?
+        .checked_sub(719468)
+}
+
+// Converts days since the unix epoch into a (year, month, day) date, see http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+use crate::request::RequestError;
+
+/// Entity tag used to validate the version of a resource, used by the `ETag`, `If-Match` and `If-None-Match` headers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ETag {
+    /// Strong entity tag, like `"abc"`.
+    Strong(String),
+
+    /// Weak entity tag, like `W/"abc"`.
+    Weak(String),
+
+    /// The `*` wildcard, matches any current entity.
+    Any,
+}
+
+impl ETag {
+    /// Compares two entity tags using the strong comparison, both need to be strong and have the same tag. [ETag::Any] matches any entity tag.
+    pub fn strong_eq(&self, other: &ETag) -> bool {
+        match (self, other) {
+            (ETag::Any, _) | (_, ETag::Any) => true,
+            (ETag::Strong(tag), ETag::Strong(other_tag)) => tag == other_tag,
+            _ => false,
+        }
+    }
+
+    /// Compares two entity tags using the weak comparison, only the tags are compared. [ETag::Any] matches any entity tag.
+    pub fn weak_eq(&self, other: &ETag) -> bool {
+        match (self, other) {
+            (ETag::Any, _) | (_, ETag::Any) => true,
+            (
+                ETag::Strong(tag) | ETag::Weak(tag),
+                ETag::Strong(other_tag) | ETag::Weak(other_tag),
+            ) => tag == other_tag,
+        }
+    }
+
+    /// Parses a comma separated list of entity tags, like the value of the `If-Match` header.
+    pub fn parse_list(list_str: &str) -> Result<Vec<ETag>, crate::Error> {
+        let etag_error =
+            || crate::Error::RequestError(RequestError::ETagError(String::from(list_str)));
+
+        let mut etags = Vec::new();
+        let mut rest = list_str.trim();
+
+        while !rest.is_empty() {
+            let (etag, remainder) = match rest {
+                _ if rest.starts_with('*') => (ETag::Any, &rest[1..]),
+                _ if rest.starts_with("W/\"") => {
+                    let end = rest[3..].find('"').ok_or_else(etag_error)? + 3;
+                    (ETag::Weak(String::from(&rest[3..end])), &rest[end + 1..])
+                }
+                _ if rest.starts_with('"') => {
+                    let end = rest[1..].find('"').ok_or_else(etag_error)? + 1;
+                    (ETag::Strong(String::from(&rest[1..end])), &rest[end + 1..])
+                }
+                _ => return Err(etag_error()),
+            };
+
+            etags.push(etag);
+
+            rest = remainder.trim_start();
+            rest = match rest.strip_prefix(',') {
+                Some(remainder) => remainder.trim_start(),
+                None if rest.is_empty() => rest,
+                None => return Err(etag_error()),
+            };
+        }
+
+        Ok(etags)
+    }
+}
+
+impl TryFrom<&str> for ETag {
+    type Error = crate::Error;
+
+    fn try_from(etag_str: &str) -> Result<Self, Self::Error> {
+        let mut etags = ETag::parse_list(etag_str)?;
+
+        match etags.len() {
+            1 => Ok(etags.remove(0)),
+            _ => Err(crate::Error::RequestError(RequestError::ETagError(
+                String::from(etag_str),
+            ))),
+        }
+    }
+}
+
+impl Display for ETag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ETag::Strong(tag) => write!(f, "\"{}\"", tag),
+            ETag::Weak(tag) => write!(f, "W/\"{}\"", tag),
+            ETag::Any => write!(f, "*"),
+        }
+    }
+}
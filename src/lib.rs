@@ -2,20 +2,32 @@
 
 //! Simple HTTP server crate that allows you to create a server and attach a router to it. The router will handle the requests and return the responses. The server listens on a given port and handles the requests using the attached router.
 
+/// Contains the functions used to format HTTP dates.
+pub mod date;
+
+/// Contains the [etag::ETag] enum, used to validate the version of a resource.
+pub mod etag;
+
 /// Contains the [package::Package] trait and its implementations for the [request::Request] and [response::Response] structs.
 pub mod package;
 
 /// Contains the [request::Request] struct, its implementations and [request::RequestError] error handling enum.
 pub mod request;
 
-/// Contains the [response::Response] struct, its implementations and [response::Response] error handling enum.
+/// Contains the [response::Response] struct, its implementations and [response::ResponseError] error handling enum.
 pub mod response;
 
+/// Contains the [version::HttpVersion] enum, used by requests and responses.
+pub mod version;
+
 /// Contains the [Router] struct, its implementations and [router::RouterError] error handling enum.
 pub mod router;
 
+#[cfg(feature = "signed-cookies")]
+mod signing;
+
 use std::{
-    io::{self, prelude::*, BufReader},
+    io,
     net::{TcpListener, TcpStream},
 };
 
@@ -46,6 +58,10 @@ pub enum Error {
     /// Checkout [request::RequestError] for more details
     #[error(transparent)]
     RequestError(#[from] request::RequestError),
+
+    /// Checkout [response::ResponseError] for more details
+    #[error(transparent)]
+    ResponseError(#[from] response::ResponseError),
 }
 
 /// Possible errors that can occur when using the [HttpServer] struct.
@@ -127,41 +143,13 @@ impl HttpServer {
     }
 
     fn handle_connection(mut stream: TcpStream, router: Router) -> Result<(), Error> {
-        let mut buf_reader = BufReader::new(&mut stream);
-
-        let mut request_bytes: Vec<u8> = Vec::new();
-        let mut body_size = 0;
-
-        loop {
-            let mut line_str = String::new();
-            let bytes_read = buf_reader.read_line(&mut line_str)?;
-
-            request_bytes.write_all(line_str.as_bytes())?;
-
-            if body_size == 0 && line_str.to_lowercase().contains("content-length") {
-                let mut parts = line_str.split(':');
-                let value = parts.nth(1).unwrap();
-
-                body_size = value.trim().parse::<usize>().unwrap();
-            }
-
-            if line_str.trim().is_empty() || bytes_read == 0 {
-                break;
-            }
-        }
-
-        let mut body_bytes_buffer = vec![0; body_size];
-        buf_reader.read_exact(&mut body_bytes_buffer)?;
-
-        request_bytes.write_all(&body_bytes_buffer)?;
-
-        let request = request::Request::try_from(request_bytes)?;
+        let request = request::Request::read_from(&mut stream, &request::ReaderConfig::default())?;
 
         let mut resp = router.handle_request(request)?;
 
         resp.pack();
 
-        stream.write_all(&resp.to_binary())?;
+        resp.write_to(&mut stream)?;
 
         Ok(())
     }
@@ -31,7 +31,7 @@ fn main() {
     router.handle_route(
         router::Route::new(request::Method::GET, "/redirect"),
         |_, mut res| {
-            res.redirect("/test");
+            res.redirect("/test").unwrap();
             res
         },
     );
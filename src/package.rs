@@ -1,5 +1,34 @@
 use std::collections::HashMap;
 
+/// Headers that only apply to a single connection and must not be forwarded by proxies.
+pub const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "Connection",
+    "Keep-Alive",
+    "Transfer-Encoding",
+    "TE",
+    "Trailer",
+    "Upgrade",
+];
+
+/// Returns true if the header only applies to a single connection (Check [HOP_BY_HOP_HEADERS], `Proxy-*` headers are included too).
+pub fn is_hop_by_hop_header(key: &str) -> bool {
+    HOP_BY_HOP_HEADERS
+        .iter()
+        .any(|header| header.eq_ignore_ascii_case(key))
+        || key.to_lowercase().starts_with("proxy-")
+}
+
+// Finds the blank line that ends the header of a package, returns the end of the header and the start of the body. `\n\n` is only used if there's no `\r\n\r\n`, so a `\n\n` inside a header doesn't hide the real boundary
+pub(crate) fn find_header_end(data: &[u8]) -> Option<(usize, usize)> {
+    [&b"\r\n\r\n"[..], &b"\n\n"[..]]
+        .iter()
+        .find_map(|separator| {
+            data.windows(separator.len())
+                .position(|window| window == *separator)
+                .map(|position| (position, position + separator.len()))
+        })
+}
+
 /// Trait used by the [crate::request::Request] and [crate::response::Response] structs in order to handle the headers and body.
 pub trait Package<BodyType> {
     /// Returns a HashMap containing the headers of the package.
@@ -8,32 +37,101 @@ pub trait Package<BodyType> {
     /// Sets all the headers of the package.
     fn set_header_list(&mut self, headers: HashMap<String, String>);
 
+    /// Replaces every header of the package at once, the previous headers are dropped. Alias of [Package::set_header_list], used with [Package::take_headers] to rewrite the headers.
+    fn set_headers(&mut self, headers: HashMap<String, String>) {
+        self.set_header_list(headers);
+    }
+
+    /// Removes every header of the package and returns them, without cloning them like [Package::get_header_list].
+    fn take_headers(&mut self) -> HashMap<String, String>;
+
     /// Sets the body of the package.
     fn set_body(&mut self, body: BodyType);
 
     /// Returns the body of the package if it exists.
     fn get_body(&self) -> Option<BodyType>;
 
-    /// Adds a header to the package.
+    /// Adds a header to the package, replacing any header with the same key (ignoring case). `Set-Cookie` values are kept as separate lines and `Vary` values are joined with commas instead of being replaced.
     fn add_header(&mut self, key: &str, value: &str) {
         let mut header_list = self.get_header_list();
-        header_list.insert(key.to_string(), value.to_string());
+
+        let previous_value = header_list
+            .keys()
+            .find(|header_key| header_key.eq_ignore_ascii_case(key))
+            .cloned()
+            .and_then(|header_key| header_list.remove(&header_key));
+
+        let value = match previous_value {
+            Some(previous) if key.eq_ignore_ascii_case("Set-Cookie") => {
+                format!("{}\n{}", previous, value)
+            }
+            Some(previous) if key.eq_ignore_ascii_case("Vary") => {
+                format!("{}, {}", previous, value)
+            }
+            _ => value.to_string(),
+        };
+
+        header_list.insert(key.to_string(), value);
         self.set_header_list(header_list);
     }
 
-    /// Checks if the package has a header with the given key.
+    /// Returns the value of the header with the given key, ignoring the case of the key.
+    fn get_header(&self, key: &str) -> Option<String> {
+        self.get_header_list()
+            .into_iter()
+            .find(|(header_key, _)| header_key.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the items of a comma separated header (Like `Accept-Encoding` or `Connection`) trimmed, skipping the empty items. Returns an empty list if there is no such header.
+    fn header_list(&self, key: &str) -> Vec<String> {
+        self.get_header(key)
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Checks if the package has a header with the given key, ignoring the case of the key.
     fn has_header(&self, key: &str) -> bool {
-        self.get_header_list().contains_key(key)
+        self.get_header_list()
+            .keys()
+            .any(|header_key| header_key.eq_ignore_ascii_case(key))
     }
 
-    /// Returns the value of the header with the given key.
+    /// Copies the headers of another package, skipping the hop-by-hop headers (Check [is_hop_by_hop_header]) and the headers listed in its `Connection` header. Used when forwarding a package to another server.
+    fn copy_end_to_end_headers_from<OtherBodyType>(&mut self, other: &impl Package<OtherBodyType>) {
+        let connection_headers: Vec<String> = other
+            .header_list("Connection")
+            .iter()
+            .map(|header| header.to_lowercase())
+            .collect();
+
+        for (key, value) in other.get_header_list() {
+            if is_hop_by_hop_header(&key) || connection_headers.contains(&key.to_lowercase()) {
+                continue;
+            }
+
+            self.add_header(&key, &value);
+        }
+    }
+
+    /// Removes the header with the given key, ignoring the case of the key.
     fn remove_header(&mut self, key: &str) {
-        self.get_header_list().remove(key);
+        let mut header_list = self.get_header_list();
+        header_list.retain(|header_key, _| !header_key.eq_ignore_ascii_case(key));
+        self.set_header_list(header_list);
     }
 }
 
 macro_rules! generate_package_getters_setters {
-    ($type:ty[$body_type:ty]) => {
+    // The cleared fields are the other bodies the package can have or the values cached from the body, setting the body resets them to their default
+    ($type:ty[$body_type:ty] $(, $cleared:ident)*) => {
         impl Package<$body_type> for $type {
             fn get_header_list(&self) -> HashMap<String, String> {
                 self.headers.clone()
@@ -43,7 +141,12 @@ macro_rules! generate_package_getters_setters {
                 self.headers = headers;
             }
 
+            fn take_headers(&mut self) -> HashMap<String, String> {
+                std::mem::take(&mut self.headers)
+            }
+
             fn set_body(&mut self, body: $body_type) {
+                $(self.$cleared = Default::default();)*
                 self.body = Some(body);
             }
 
@@ -0,0 +1,162 @@
+use super::RequestError;
+
+/// Credentials of the `Authorization` header, check [crate::request::Request::auth].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Auth {
+    /// A `Bearer` token (RFC 6750).
+    Bearer(String),
+
+    /// `Digest` credentials (RFC 7616).
+    Digest(DigestAuth),
+
+    /// Credentials of any other scheme, made of the scheme and the raw credentials.
+    Other(String, String),
+}
+
+/// Parameters of `Digest` credentials. Unknown parameters are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DigestAuth {
+    /// Name of the user.
+    pub username: String,
+
+    /// Protection space of the credentials.
+    pub realm: String,
+
+    /// Nonce sent by the server in the challenge.
+    pub nonce: String,
+
+    /// Target of the request.
+    pub uri: String,
+
+    /// Hex digest proving that the user knows the password.
+    pub response: String,
+
+    /// Quality of protection applied, like `auth`.
+    pub qop: Option<String>,
+
+    /// Nonce count, required if `qop` is set.
+    pub nc: Option<String>,
+
+    /// Nonce generated by the client, required if `qop` is set.
+    pub cnonce: Option<String>,
+}
+
+impl TryFrom<&str> for Auth {
+    type Error = crate::Error;
+
+    fn try_from(auth_str: &str) -> Result<Self, Self::Error> {
+        let trimmed_auth_str = auth_str.trim();
+
+        let (scheme, credentials) = trimmed_auth_str
+            .split_once(' ')
+            .unwrap_or((trimmed_auth_str, ""));
+        let credentials = credentials.trim();
+
+        if scheme.is_empty() {
+            return Err(crate::Error::RequestError(RequestError::AuthError(
+                String::from(auth_str),
+            )));
+        }
+
+        match scheme.to_lowercase().as_str() {
+            "bearer" => Ok(Auth::Bearer(String::from(credentials))),
+            "digest" => Ok(Auth::Digest(DigestAuth::try_from(credentials)?)),
+            _ => Ok(Auth::Other(String::from(scheme), String::from(credentials))),
+        }
+    }
+}
+
+impl TryFrom<&str> for DigestAuth {
+    type Error = crate::Error;
+
+    /// Parses the comma-separated `key=value` parameters of `Digest` credentials, values can be quoted strings. Returns a [RequestError::AuthError] if a pair is malformed or a required parameter is missing.
+    fn try_from(credentials: &str) -> Result<Self, Self::Error> {
+        let auth_error =
+            || crate::Error::RequestError(RequestError::AuthError(String::from(credentials)));
+
+        let mut digest = DigestAuth::default();
+        let mut required = [false; 5];
+
+        for (key, value) in parse_pairs(credentials).ok_or_else(auth_error)? {
+            let (field, index) = match key.as_str() {
+                "username" => (&mut digest.username, Some(0)),
+                "realm" => (&mut digest.realm, Some(1)),
+                "nonce" => (&mut digest.nonce, Some(2)),
+                "uri" => (&mut digest.uri, Some(3)),
+                "response" => (&mut digest.response, Some(4)),
+                "qop" => (digest.qop.insert(String::new()), None),
+                "nc" => (digest.nc.insert(String::new()), None),
+                "cnonce" => (digest.cnonce.insert(String::new()), None),
+                _ => continue,
+            };
+
+            *field = value;
+
+            if let Some(index) = index {
+                required[index] = true;
+            }
+        }
+
+        match required.iter().all(|found| *found) {
+            true => Ok(digest),
+            false => Err(auth_error()),
+        }
+    }
+}
+
+// Splits the pairs on the commas outside quoted strings, keys are lowercased and quoted values are unescaped. Returns None if a pair is malformed
+fn parse_pairs(credentials: &str) -> Option<Vec<(String, String)>> {
+    let mut pairs = Vec::new();
+    let mut chars = credentials.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+
+        if chars.peek().is_none() {
+            return Some(pairs);
+        }
+
+        let key: String =
+            std::iter::from_fn(|| chars.next_if(|c| *c != '=' && *c != ',')).collect();
+        let key = key.trim().to_lowercase();
+
+        if key.is_empty() || chars.next() != Some('=') {
+            return None;
+        }
+
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let value = match chars.next_if_eq(&'"') {
+            Some(_) => {
+                let mut value = String::new();
+
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => value.push(chars.next()?),
+                        c => value.push(c),
+                    }
+                }
+
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+                if chars.next_if(|c| *c != ',').is_some() {
+                    return None;
+                }
+
+                value
+            }
+            None => {
+                let value: String = std::iter::from_fn(|| chars.next_if(|c| *c != ',')).collect();
+
+                if value.trim().is_empty() || value.contains('"') {
+                    return None;
+                }
+
+                String::from(value.trim())
+            }
+        };
+
+        pairs.push((key, value));
+    }
+}
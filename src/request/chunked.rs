@@ -0,0 +1,147 @@
+use std::io::{BufRead, Read};
+
+use super::{ParseConfig, RequestError};
+
+/// Reads a body encoded with `Transfer-Encoding: chunked` from the reader, stopping after the last chunk and its trailers.
+///
+/// Every chunk size is checked against [ParseConfig::max_chunk_size] and [ParseConfig::max_body_size] before reading the chunk, so a huge declared size fails before allocating any memory. Lines longer than [ParseConfig::max_line_length] are rejected.
+///
+/// In strict mode a `Content-Length` trailer declaring the total size of the body is validated against the decoded size, a mismatch returns a [RequestError::ConflictingFraming].
+pub(crate) fn read_chunked_body<R: BufRead>(
+    reader: &mut R,
+    config: &ParseConfig,
+) -> Result<Vec<u8>, crate::Error> {
+    let mut body = Vec::new();
+
+    loop {
+        let chunk_size = read_chunk_size(reader, config)?;
+
+        if body.len() + chunk_size > config.max_body_size {
+            return Err(crate::Error::RequestError(RequestError::BodyTooLarge(
+                config.max_body_size,
+            )));
+        }
+
+        if chunk_size == 0 {
+            break;
+        }
+
+        let start = body.len();
+        body.resize(start + chunk_size, 0);
+        reader.read_exact(&mut body[start..])?;
+
+        read_chunk_end(reader, config)?;
+    }
+
+    // Trailers are read until the blank line that ends the body
+    loop {
+        let trailer = read_line(reader, config)?;
+
+        if trailer.is_empty() {
+            break;
+        }
+
+        // A declared total that contradicts the decoded size means the framing was tampered with
+        let declared_total = match trailer.split_once(':') {
+            Some((key, value))
+                if config.strict && key.trim().eq_ignore_ascii_case("Content-Length") =>
+            {
+                value.trim()
+            }
+            _ => continue,
+        };
+
+        if declared_total != body.len().to_string() {
+            return Err(crate::Error::RequestError(
+                RequestError::ConflictingFraming(format!(
+                    "Decoded {} bytes but the chunked body declares {}",
+                    body.len(),
+                    declared_total
+                )),
+            ));
+        }
+    }
+
+    Ok(body)
+}
+
+// Reads the size line of a chunk and checks the size against the limits of the config. Chunk extensions (`;name=value`) are ignored
+pub(super) fn read_chunk_size<R: BufRead>(
+    reader: &mut R,
+    config: &ParseConfig,
+) -> Result<usize, crate::Error> {
+    let size_line = read_line(reader, config)?;
+
+    let size_str = size_line.split(';').next().unwrap_or("").trim();
+
+    // The size is only hex digits (`1*HEXDIG`), from_str_radix would also accept a sign like `+3` that other servers may read differently
+    if size_str.is_empty() || !size_str.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(crate::Error::RequestError(RequestError::InvalidChunk(
+            size_line,
+        )));
+    }
+
+    let chunk_size = usize::from_str_radix(size_str, 16).map_err(|err| match err.kind() {
+        std::num::IntErrorKind::PosOverflow => {
+            crate::Error::RequestError(RequestError::BodyTooLarge(config.max_body_size))
+        }
+        _ => crate::Error::RequestError(RequestError::InvalidChunk(size_line.clone())),
+    })?;
+
+    if chunk_size > config.max_chunk_size {
+        return Err(crate::Error::RequestError(RequestError::BodyTooLarge(
+            config.max_chunk_size,
+        )));
+    }
+
+    Ok(chunk_size)
+}
+
+// Reads the line break that follows the data of a chunk
+pub(super) fn read_chunk_end<R: BufRead>(
+    reader: &mut R,
+    config: &ParseConfig,
+) -> Result<(), crate::Error> {
+    let chunk_end = read_line(reader, config)?;
+
+    match chunk_end.is_empty() {
+        true => Ok(()),
+        false => Err(crate::Error::RequestError(RequestError::InvalidChunk(
+            chunk_end,
+        ))),
+    }
+}
+
+pub(super) fn read_line<R: BufRead>(
+    reader: &mut R,
+    config: &ParseConfig,
+) -> Result<String, crate::Error> {
+    let mut line = Vec::new();
+
+    // The line break is allowed on top of the limit, so the read stops right after a line that's too long
+    reader
+        .by_ref()
+        .take(config.max_line_length as u64 + 2)
+        .read_until(b'\n', &mut line)?;
+
+    let complete = line.ends_with(b"\n");
+
+    while line.ends_with(b"\n") || line.ends_with(b"\r") {
+        line.pop();
+    }
+
+    if line.len() > config.max_line_length {
+        return Err(crate::Error::RequestError(RequestError::LineTooLong(
+            config.max_line_length,
+        )));
+    }
+
+    // A line without its line break means the body ended in the middle of the line
+    if !complete {
+        return Err(crate::Error::Io(std::io::Error::from(
+            std::io::ErrorKind::UnexpectedEof,
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&line).to_string())
+}
@@ -0,0 +1,109 @@
+use std::io::{self, BufRead, Read};
+
+use super::{chunked, ParseConfig, RequestError};
+
+/// Reader that decodes a body encoded with `Transfer-Encoding: chunked` as it's read, without buffering the whole body. Check [crate::request::Request::chunked_body_reader].
+///
+/// Reads stop at the last chunk, once its trailers have been read. The chunks are checked against the limits of the [ParseConfig] like [crate::request::Request::from_buf_read_with_config] does. Malformed chunks or chunks over the limits return an [io::ErrorKind::InvalidData] error and bodies that end in the middle of a chunk an [io::ErrorKind::UnexpectedEof] error.
+#[derive(Debug)]
+pub struct ChunkedReader<R> {
+    reader: R,
+    config: ParseConfig,
+    body_len: usize,
+    state: ChunkState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkState {
+    Size,
+    Data(usize),
+    Done,
+}
+
+impl<R: BufRead> ChunkedReader<R> {
+    /// Generates a new decoder reading the chunked body from the reader, using the default parsing options.
+    pub fn new(reader: R) -> Self {
+        ChunkedReader::with_config(reader, &ParseConfig::default())
+    }
+
+    /// Same as [ChunkedReader::new] using the given parsing options.
+    pub fn with_config(reader: R, config: &ParseConfig) -> Self {
+        ChunkedReader {
+            reader,
+            config: config.clone(),
+            body_len: 0,
+            state: ChunkState::Size,
+        }
+    }
+
+    /// Returns the inner reader, positioned right after the last byte of the chunked body that has been read.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+// The decoding errors are returned as io errors, keeping the kind of the io errors of the inner reader
+fn into_io_error(err: crate::Error) -> io::Error {
+    match err {
+        crate::Error::Io(err) => err,
+        err => io::Error::new(io::ErrorKind::InvalidData, err),
+    }
+}
+
+impl<R: BufRead> Read for ChunkedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            match self.state {
+                ChunkState::Done => return Ok(0),
+                ChunkState::Size => {
+                    let chunk_size = chunked::read_chunk_size(&mut self.reader, &self.config)
+                        .map_err(into_io_error)?;
+
+                    if self.body_len + chunk_size > self.config.max_body_size {
+                        return Err(into_io_error(crate::Error::RequestError(
+                            RequestError::BodyTooLarge(self.config.max_body_size),
+                        )));
+                    }
+
+                    self.body_len += chunk_size;
+
+                    if chunk_size > 0 {
+                        self.state = ChunkState::Data(chunk_size);
+                        continue;
+                    }
+
+                    // Trailers are read until the blank line that ends the body
+                    while !chunked::read_line(&mut self.reader, &self.config)
+                        .map_err(into_io_error)?
+                        .is_empty()
+                    {}
+
+                    self.state = ChunkState::Done;
+                }
+                ChunkState::Data(remaining) => {
+                    let max_len = remaining.min(buf.len());
+                    let bytes_read = self.reader.read(&mut buf[..max_len])?;
+
+                    if bytes_read == 0 {
+                        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+                    }
+
+                    self.state = ChunkState::Data(remaining - bytes_read);
+
+                    if remaining == bytes_read {
+                        chunked::read_chunk_end(&mut self.reader, &self.config)
+                            .map_err(into_io_error)?;
+
+                        self.state = ChunkState::Size;
+                    }
+
+                    return Ok(bytes_read);
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use super::RequestError;
+use super::{ParseConfig, RequestError};
 
 /// Contains a list of cookies.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -11,42 +11,9 @@ pub struct CookieList {
 impl TryFrom<&str> for CookieList {
     type Error = crate::Error;
 
+    /// Parses a cookie list, failing on malformed pairs.
     fn try_from(cookie_list_str: &str) -> Result<Self, Self::Error> {
-        let mut cookie_list = CookieList::new();
-
-        let trimmed_cookie_list_str = cookie_list_str.trim_start_matches("Cookie: ");
-        let cookie_pairs = trimmed_cookie_list_str.split("; ");
-
-        for cookie_pair in cookie_pairs {
-            if cookie_pair.trim().is_empty() {
-                break;
-            }
-
-            let mut splited_cookie_pair = cookie_pair.split('=');
-
-            let cookie_key = match splited_cookie_pair.next() {
-                Some(key) => key,
-                None => {
-                    return Err(crate::Error::RequestError(RequestError::CookieError(
-                        String::from(cookie_list_str),
-                    )))
-                }
-            };
-
-            let cookie_value = match splited_cookie_pair.next() {
-                Some(key) => key,
-                None => {
-                    return Err(crate::Error::RequestError(RequestError::CookieError(
-                        String::from(cookie_list_str),
-                    )))
-                }
-            }
-            .trim_end_matches(';');
-
-            cookie_list.set(cookie_key, cookie_value);
-        }
-
-        Ok(cookie_list)
+        CookieList::parse(cookie_list_str, &ParseConfig::strict())
     }
 }
 
@@ -64,6 +31,33 @@ impl CookieList {
         }
     }
 
+    /// Parses a cookie list using the given parsing options. Malformed pairs (without `=` or with an empty name) are reported with a [RequestError::CookieError] in strict mode, and skipped in lenient mode like browsers do.
+    pub fn parse(cookie_list_str: &str, config: &ParseConfig) -> Result<Self, crate::Error> {
+        let mut cookie_list = CookieList::new();
+
+        let trimmed_cookie_list_str = cookie_list_str.trim_start_matches("Cookie: ");
+
+        for cookie_pair in trimmed_cookie_list_str.split(';').map(str::trim) {
+            if cookie_pair.is_empty() {
+                continue;
+            }
+
+            match cookie_pair.split_once('=') {
+                Some((cookie_key, cookie_value)) if !cookie_key.trim().is_empty() => {
+                    cookie_list.set(cookie_key.trim(), cookie_value.trim())
+                }
+                _ if config.strict => {
+                    return Err(crate::Error::RequestError(RequestError::CookieError(
+                        String::from(cookie_pair),
+                    )))
+                }
+                _ => (),
+            }
+        }
+
+        Ok(cookie_list)
+    }
+
     /// Adds a new cookie to the list.
     pub fn set(&mut self, name: &str, value: &str) {
         self.cookies.insert(String::from(name), String::from(value));
@@ -0,0 +1,33 @@
+use std::io::{self, BufRead, Read};
+
+/// Reader that counts the bytes consumed from the inner reader, used to measure the size of the body on the wire.
+pub(crate) struct CountingReader<'a, R> {
+    inner: &'a mut R,
+    pub(crate) count: usize,
+}
+
+impl<'a, R> CountingReader<'a, R> {
+    pub(crate) fn new(inner: &'a mut R) -> Self {
+        CountingReader { inner, count: 0 }
+    }
+}
+
+impl<R: Read> Read for CountingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        self.count += bytes_read;
+
+        Ok(bytes_read)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<'_, R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.count += amt;
+        self.inner.consume(amt);
+    }
+}
@@ -0,0 +1,46 @@
+/// Set of characters that are left as is when percent-encoding a component of an URL, based on the RFC 3986 grammar. Existing `%XX` sequences are kept so already encoded components aren't encoded twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EncodeSet {
+    /// Characters allowed in a path, segment characters (`pchar`) and `/`.
+    #[default]
+    Path,
+
+    /// Characters allowed in a query, segment characters, `/` and `?`.
+    Query,
+
+    /// Characters allowed in the user information of an authority, unreserved characters, sub-delimiters and `:`.
+    Userinfo,
+}
+
+impl EncodeSet {
+    /// Percent-encodes the bytes of the input not allowed by the set.
+    pub fn encode(&self, input: &str) -> String {
+        let bytes = input.as_bytes();
+        let mut encoded = String::with_capacity(bytes.len());
+
+        for (index, byte) in bytes.iter().enumerate() {
+            let is_escape = *byte == b'%'
+                && bytes.len() > index + 2
+                && bytes[index + 1].is_ascii_hexdigit()
+                && bytes[index + 2].is_ascii_hexdigit();
+
+            match is_escape || self.allows(*byte) {
+                true => encoded.push(*byte as char),
+                false => encoded.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+
+        encoded
+    }
+
+    fn allows(&self, byte: u8) -> bool {
+        let unreserved = byte.is_ascii_alphanumeric() || b"-._~".contains(&byte);
+        let sub_delimiter = b"!$&'()*+,;=".contains(&byte);
+
+        match self {
+            EncodeSet::Path => unreserved || sub_delimiter || b":@/".contains(&byte),
+            EncodeSet::Query => unreserved || sub_delimiter || b":@/?".contains(&byte),
+            EncodeSet::Userinfo => unreserved || sub_delimiter || byte == b':',
+        }
+    }
+}
@@ -1,3 +1,7 @@
+use std::fmt::Display;
+
+use super::ParseConfig;
+
 /// Contains all the supported request methods.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[allow(missing_docs)]
@@ -7,6 +11,9 @@ pub enum Method {
     PUT,
     DELETE,
     HEAD,
+    PATCH,
+    OPTIONS,
+    /// Any other method, like the WebDAV `PROPFIND` or `MKCOL` methods. Method names are case-sensitive, `Other("PROPFIND")` and `Other("propfind")` are different methods.
     Other(String),
 }
 
@@ -18,12 +25,33 @@ macro_rules! gen_try_from_and_from {
             fn try_from(method_str: &str) -> Result<Self, Self::Error> {
                 match method_str {
                     $($method => Ok($request_type),)*
-                    _ => Err("Request met"),
+                    _ => Err("Invalid request method"),
                 }
             }
         }
 
+        impl Display for Method {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                if let Method::Other(method) = self {
+                    return write!(f, "{}", method);
+                }
+
+                $(
+                    if *self == $request_type {
+                        return write!(f, "{}", $method);
+                    }
+                )*
+
+                Ok(())
+            }
+        }
+
         impl Method {
+            /// Returns every supported method (Excluding [Method::Other]), always in the same order.
+            pub fn all() -> &'static [Method] {
+                &[$($request_type),*]
+            }
+
             /// Generates a request method from a string. If the method is not supported, it will return [RequestMethod::Other] with the method string inside.
             /// Use preferably [RequestMethod::try_from] instead.
             pub fn from(method_str: &str) -> Self {
@@ -42,5 +70,31 @@ gen_try_from_and_from!(
     "POST" => Method::POST,
     "PUT" => Method::PUT,
     "DELETE" => Method::DELETE,
-    "HEAD" => Method::HEAD
+    "HEAD" => Method::HEAD,
+    "PATCH" => Method::PATCH,
+    "OPTIONS" => Method::OPTIONS
 );
+
+impl Method {
+    /// Parses a request method using the given parsing options. Lenient parsing trims and uppercases the method, any other method made only of token characters (RFC 9110) is returned as [Method::Other] with its uppercase name, so it matches the routes registered with `Method::from("PROPFIND")`. Strict parsing only accepts the known methods, with their exact uppercase name.
+    pub fn parse(method_str: &str, config: &ParseConfig) -> Result<Self, &'static str> {
+        if config.strict {
+            return Method::try_from(method_str);
+        }
+
+        let method_str = method_str.trim().to_uppercase();
+
+        match Method::try_from(method_str.as_str()) {
+            Ok(method) => Ok(method),
+            Err(_) if is_token(&method_str) => Ok(Method::Other(method_str)),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn is_token(method_str: &str) -> bool {
+    !method_str.is_empty()
+        && method_str
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte))
+}
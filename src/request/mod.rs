@@ -1,17 +1,50 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    sync::OnceLock,
+    time::SystemTime,
+};
 
+use crate::date::parse_http_date;
+use crate::etag::ETag;
 use crate::package;
+use crate::request::counting_reader::CountingReader;
 use crate::router::Route;
+use crate::version::HttpVersion;
+use crate::BinaryRepresentation;
 
 pub use crate::package::Package;
 
+mod auth;
+pub(crate) mod chunked;
+mod chunked_reader;
 mod cookie_list;
+mod counting_reader;
+mod encode_set;
+mod extensions;
 mod method;
+mod origin;
+mod parse_config;
+mod parse_status;
+mod quality;
 mod query;
+mod range;
+mod reader_config;
+mod url;
 
+pub use auth::{Auth, DigestAuth};
+pub use chunked_reader::ChunkedReader;
 pub use cookie_list::CookieList;
+pub use encode_set::EncodeSet;
+pub use extensions::Extensions;
 pub use method::Method;
-pub use query::Query;
+pub use origin::Origin;
+pub use parse_config::ParseConfig;
+pub use parse_status::ParseStatus;
+pub use query::{Query, QueryDecodeMode, QueryIter};
+pub use range::{ByteRange, Range};
+pub use reader_config::ReaderConfig;
+pub use url::Url;
 
 /// Represents a request made by a client.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -25,26 +58,98 @@ pub struct Request {
     /// The cookies of the request.
     pub cookies: CookieList,
 
+    /// The HTTP version of the request.
+    pub version: HttpVersion,
+
+    /// Values attached to the request by middlewares, ignored when comparing requests.
+    pub extensions: Extensions,
+
     headers: HashMap<String, String>,
     body: Option<Vec<u8>>,
+
+    form: FormCache,
+
+    raw_path: Option<String>,
+    byte_lengths: ByteLengths,
+}
+
+// Sizes on the wire of a parsed request, they are ignored when comparing requests like the extensions
+#[derive(Debug, Clone, Copy, Default, Eq)]
+struct ByteLengths {
+    header: usize,
+    body: usize,
+}
+
+impl PartialEq for ByteLengths {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+// Form parsed from the body by Request::form_mut, it's ignored when comparing requests since it's only a cache of the body
+#[derive(Debug, Clone, Default, Eq)]
+struct FormCache(Option<Query>);
+
+impl PartialEq for FormCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
-package::generate_package_getters_setters!(Request[Vec<u8>]);
+package::generate_package_getters_setters!(Request[Vec<u8>], form);
+
+// Splits the header from the body, check package::find_header_end. Data without a blank line is all body
+macro_rules! split_sequence {
+    ($sequence:expr) => {{
+        let sequence: &[u8] = $sequence;
+
+        match package::find_header_end(sequence) {
+            Some((header_end, body_start)) => (
+                sequence[..header_end].to_vec(),
+                sequence[body_start..].to_vec(),
+            ),
+            None => (Vec::new(), sequence.to_vec()),
+        }
+    }};
+}
 
 impl Request {
     /// Generates a new request method, with the given method and path.
     pub fn new(method: Method, path: &str, query: Option<Query>) -> Self {
-        let path = Route::new(method, path);
+        let route = Route::new(method, path);
 
         Request {
-            path,
+            path: route,
             headers: HashMap::new(),
             query,
             cookies: CookieList::new(),
+            version: HttpVersion::default(),
+            extensions: Extensions::new(),
             body: None,
+            form: FormCache::default(),
+            raw_path: None,
+            byte_lengths: ByteLengths::default(),
         }
     }
 
+    /// Adds a header to the request and returns it, check [Package::add_header]. Allows building requests by chaining calls.
+    pub fn with_header(mut self, key: &str, value: &str) -> Self {
+        self.add_header(key, value);
+        self
+    }
+
+    /// Sets a cookie of the request and returns it, check [CookieList::set].
+    pub fn with_cookie(mut self, name: &str, value: &str) -> Self {
+        self.cookies.set(name, value);
+        self
+    }
+
+    /// Sets the body of the request and returns it, check [Package::set_body]. The form parsed by [Request::form_mut] is discarded.
+    pub fn with_body(mut self, body: Vec<u8>) -> Self {
+        self.set_body(body);
+        self
+    }
+
     /// Returns the body of the request as a string.
     pub fn get_body_string(&self) -> String {
         match &self.body {
@@ -53,10 +158,597 @@ impl Request {
         }
     }
 
-    fn parse_header_str(header_string: &str) -> Result<Request, crate::Error> {
+    /// Returns a copy of the request without its body (And without the form parsed from it), the rest of the request is kept intact. Used to keep cheap snapshots of requests for logging.
+    pub fn clone_without_body(&self) -> Request {
+        Request {
+            path: self.path.clone(),
+            query: self.query.clone(),
+            cookies: self.cookies.clone(),
+            version: self.version.clone(),
+            extensions: self.extensions.clone(),
+            headers: self.headers.clone(),
+            body: None,
+            form: FormCache::default(),
+            raw_path: self.raw_path.clone(),
+            byte_lengths: self.byte_lengths,
+        }
+    }
+
+    /// Returns a reader that decodes the chunked body read from the reader as it's read, so it can be forwarded without buffering it. Used with the reader positioned after the header of a request with `Transfer-Encoding: chunked`, the bytes after the body stay in the reader (Check [ChunkedReader::into_inner]).
+    pub fn chunked_body_reader<R: BufRead>(reader: R) -> ChunkedReader<R> {
+        ChunkedReader::new(reader)
+    }
+
+    /// Returns the length in bytes of the received body, once it has been decoded (The reassembled length for chunked bodies). Doesn't rely on the `Content-Length` header.
+    pub fn effective_body_len(&self) -> usize {
+        self.body.as_ref().map_or(0, |body| body.len())
+    }
+
+    /// Returns the number of bytes the request line and headers used on the wire, including the line breaks and the blank line that ends them. It's 0 unless the request was parsed from bytes or a reader (Check [Request::parse_with_config] and [Request::from_buf_read]).
+    pub fn header_byte_len(&self) -> usize {
+        self.byte_lengths.header
+    }
+
+    /// Returns the number of bytes the body used on the wire, before decoding it (Including the chunk sizes of chunked bodies). It's 0 unless the request was parsed from bytes or a reader.
+    pub fn body_byte_len(&self) -> usize {
+        self.byte_lengths.body
+    }
+
+    /// Returns the path of the request target exactly as it was received, without the query and before decoding it (The path of [Request::path] is percent-decoded, except for encoded slashes). Requests that weren't parsed return the path they were generated with.
+    pub fn raw_path(&self) -> &str {
+        self.raw_path.as_deref().unwrap_or(&self.path.path)
+    }
+
+    /// Returns an iterator over the non-empty segments of the path, like the segments of the normalized path (Check [crate::router::normalize_path]), `/a//b/` yields `a` and `b`.
+    pub fn path_segments(&self) -> impl Iterator<Item = &str> {
+        self.path
+            .path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+    }
+
+    /// Returns an iterator over the names of the headers, in arbitrary order and with the casing sent by the client.
+    pub fn header_names(&self) -> impl Iterator<Item = &str> {
+        self.headers.keys().map(String::as_str)
+    }
+
+    /// Returns the query of the request, or an empty query if the request has none.
+    pub fn query_or_empty(&self) -> &Query {
+        static EMPTY_QUERY: OnceLock<Query> = OnceLock::new();
+
+        match &self.query {
+            Some(query) => query,
+            None => EMPTY_QUERY.get_or_init(Query::new),
+        }
+    }
+
+    /// Parses a request from a reader, reading the header line by line until the blank line and then the body using the `Content-Length` or `Transfer-Encoding: chunked` headers.
+    pub fn from_buf_read<R: BufRead>(reader: &mut R) -> Result<Request, crate::Error> {
+        Request::from_buf_read_with_config(reader, &ParseConfig::default())
+    }
+
+    /// Same as [Request::from_buf_read] using the given parsing options.
+    pub fn from_buf_read_with_config<R: BufRead>(
+        reader: &mut R,
+        config: &ParseConfig,
+    ) -> Result<Request, crate::Error> {
+        let mut request = Request::read_head(reader, config)?;
+        request.read_body(reader, config)?;
+
+        Ok(request)
+    }
+
+    /// Reads a request from a stream. If [ReaderConfig::auto_continue] is enabled and the request has the `Expect: 100-continue` header, a `100 Continue` interim response is written to the stream before reading the body, unless the declared `Content-Length` is over [ParseConfig::max_body_size].
+    ///
+    /// The stream is read using an internal buffer, any bytes sent after the request (pipelined requests) are discarded.
+    pub fn read_from<S: Read + Write>(
+        stream: &mut S,
+        config: &ReaderConfig,
+    ) -> Result<Request, crate::Error> {
+        let mut reader = BufReader::new(stream);
+
+        let mut request = Request::read_head(&mut reader, &config.parse_config)?;
+
+        if config.auto_continue && request.expects_continue() {
+            // The client mustn't be asked to send a body that will be refused, so the declared length is checked first
+            if !request.is_chunked() {
+                request.declared_content_length(&config.parse_config)?;
+            }
+
+            let interim_response =
+                crate::response::Response::new(crate::response::Status::Continue);
+
+            let stream = reader.get_mut();
+            interim_response.write_to(stream)?;
+            stream.flush()?;
+        }
+
+        request.read_body(&mut reader, &config.parse_config)?;
+
+        Ok(request)
+    }
+
+    /// Returns true if the request has the `Expect: 100-continue` header.
+    pub fn expects_continue(&self) -> bool {
+        self.get_header("Expect")
+            .is_some_and(|expect| expect.trim().eq_ignore_ascii_case("100-continue"))
+    }
+
+    fn read_head<R: BufRead>(
+        reader: &mut R,
+        config: &ParseConfig,
+    ) -> Result<Request, crate::Error> {
+        let mut header_bytes: Vec<u8> = Vec::new();
+        let mut header_byte_len = 0;
+        let mut line_count = 0;
+
+        loop {
+            let mut line = Vec::new();
+
+            // The read stops one byte after the limit, so a client that never ends the header can't grow the buffer
+            let bytes_read = reader
+                .by_ref()
+                .take((config.max_header_size - header_byte_len) as u64 + 1)
+                .read_until(b'\n', &mut line)?;
+            header_byte_len += bytes_read;
+
+            if header_byte_len > config.max_header_size {
+                return Err(crate::Error::RequestError(RequestError::HeaderTooLarge(
+                    config.max_header_size,
+                )));
+            }
+
+            if bytes_read == 0 || line.trim_ascii().is_empty() {
+                break;
+            }
+
+            // The request line doesn't count as a header
+            if line_count > config.max_headers {
+                return Err(crate::Error::RequestError(RequestError::TooManyHeaders(
+                    config.max_headers,
+                )));
+            }
+
+            line_count += 1;
+            header_bytes.extend_from_slice(&line);
+        }
+
+        let mut request =
+            Request::parse_header_str(&String::from_utf8_lossy(&header_bytes), config)?;
+        request.byte_lengths.header = header_byte_len;
+
+        Ok(request)
+    }
+
+    fn read_body<R: BufRead>(
+        &mut self,
+        reader: &mut R,
+        config: &ParseConfig,
+    ) -> Result<(), crate::Error> {
+        let mut reader = CountingReader::new(reader);
+
+        let body = match self.is_chunked() {
+            true => chunked::read_chunked_body(&mut reader, config)?,
+            false => {
+                let mut body = vec![0; self.declared_content_length(config)?];
+                reader.read_exact(&mut body)?;
+                body
+            }
+        };
+
+        self.byte_lengths.body = reader.count;
+        self.set_body(body);
+
+        Ok(())
+    }
+
+    /// Parses a request (header and body) using the given parsing options.
+    pub fn parse_with_config(data: &[u8], config: &ParseConfig) -> Result<Request, crate::Error> {
+        let (header, body) = split_sequence!(data);
+
+        let header_string = String::from_utf8_lossy(&header);
+
+        let mut request = Request::parse_header_str(header_string.as_ref(), config)?;
+
+        let body_byte_len = body.len();
+
+        let body = match request.is_chunked() {
+            true => {
+                let mut remaining = body.as_slice();
+                let decoded_body = chunked::read_chunked_body(&mut remaining, config)?;
+
+                request.byte_lengths.body = body_byte_len - remaining.len();
+                decoded_body
+            }
+            false => {
+                request.declared_content_length(config)?;
+                request.byte_lengths.body = body_byte_len;
+                body
+            }
+        };
+
+        request.byte_lengths.header = data.len() - body_byte_len;
+        request.set_body(body);
+
+        Ok(request)
+    }
+
+    /// Parses a request from the start of a buffer that may not have received the whole request yet, like the buffer of an event loop. Returns [ParseStatus::Incomplete] with the number of bytes still needed when it can be derived from the `Content-Length`, the bytes after a complete request are left for the next one.
+    pub fn parse_prefix(data: &[u8], config: &ParseConfig) -> Result<ParseStatus, crate::Error> {
+        let (header_end, body_start) = match package::find_header_end(data) {
+            Some(boundary) => boundary,
+            None => return Ok(ParseStatus::Incomplete(None)),
+        };
+
+        let mut request =
+            Request::parse_header_str(&String::from_utf8_lossy(&data[..header_end]), config)?;
+        request.byte_lengths.header = body_start;
+
+        let mut body_data = &data[body_start..];
+
+        let body = match request.is_chunked() {
+            true => match chunked::read_chunked_body(&mut body_data, config) {
+                Ok(body) => body,
+                Err(crate::Error::Io(err)) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                    return Ok(ParseStatus::Incomplete(None))
+                }
+                Err(err) => return Err(err),
+            },
+            false => {
+                let content_length = request.declared_content_length(config)?;
+
+                if body_data.len() < content_length {
+                    return Ok(ParseStatus::Incomplete(Some(
+                        content_length - body_data.len(),
+                    )));
+                }
+
+                let body = body_data[..content_length].to_vec();
+                body_data = &body_data[content_length..];
+                body
+            }
+        };
+
+        request.byte_lengths.body = data.len() - body_start - body_data.len();
+        request.set_body(body);
+
+        let consumed = data.len() - body_data.len();
+
+        Ok(ParseStatus::Complete(Box::new(request), consumed))
+    }
+
+    fn is_chunked(&self) -> bool {
+        self.get_header("Transfer-Encoding")
+            .is_some_and(|encoding| encoding.to_lowercase().contains("chunked"))
+    }
+
+    fn declared_content_length(&self, config: &ParseConfig) -> Result<usize, crate::Error> {
+        // Only digits are accepted, signs, inner whitespace and lists of values are handled differently by other parsers and can be used for request smuggling
+        let content_length = match self.get_header("Content-Length") {
+            Some(content_length) => {
+                let trimmed_content_length = content_length.trim();
+
+                if trimmed_content_length.is_empty()
+                    || !trimmed_content_length
+                        .bytes()
+                        .all(|byte| byte.is_ascii_digit())
+                {
+                    return Err(crate::Error::RequestError(
+                        RequestError::InvalidContentLength(content_length),
+                    ));
+                }
+
+                trimmed_content_length.parse::<usize>().map_err(|_| {
+                    crate::Error::RequestError(RequestError::BodyTooLarge(config.max_body_size))
+                })?
+            }
+            None => 0,
+        };
+
+        if content_length > config.max_body_size {
+            return Err(crate::Error::RequestError(RequestError::BodyTooLarge(
+                config.max_body_size,
+            )));
+        }
+
+        Ok(content_length)
+    }
+
+    /// Returns the method the request should be handled as. If `allow_override` is true and the request method is POST, the `X-HTTP-Method-Override` header or the `_method` field of an urlencoded form body are honored. Invalid override values fall back to the request method.
+    pub fn effective_method(&self, allow_override: bool) -> Method {
+        if !allow_override || self.path.method != Method::POST {
+            return self.path.method.clone();
+        }
+
+        let override_header = self.get_header("X-HTTP-Method-Override");
+
+        let override_field = match self.get_header("Content-Type") {
+            Some(content_type) if content_type.starts_with("application/x-www-form-urlencoded") => {
+                Query::try_from(self.get_body_string().as_str())
+                    .ok()
+                    .and_then(|form| form.get("_method").cloned())
+            }
+            _ => None,
+        };
+
+        override_header
+            .or(override_field)
+            .and_then(|method| Method::try_from(method.trim().to_uppercase().as_str()).ok())
+            .unwrap_or_else(|| self.path.method.clone())
+    }
+
+    /// Returns true if the request was made with `XMLHttpRequest` by a script, using the `X-Requested-With` header sent by most JavaScript libraries.
+    pub fn is_ajax(&self) -> bool {
+        self.get_header("X-Requested-With")
+            .is_some_and(|requested_with| {
+                requested_with.trim().eq_ignore_ascii_case("XMLHttpRequest")
+            })
+    }
+
+    /// Returns true if the request targets the whole server instead of a resource (`OPTIONS * HTTP/1.1`), its path is `*`. Used to ask for the capabilities of the server.
+    pub fn is_asterisk_form(&self) -> bool {
+        self.path.path == "*" && self.path.method == Method::OPTIONS
+    }
+
+    /// Returns the preferences of the `Prefer` header (RFC 7240), like `return=minimal`. Names are lowercased, quoted values are unquoted and preferences without value are returned with an empty value. Preference parameters (After `;`) are ignored.
+    pub fn prefer(&self) -> HashMap<String, String> {
+        let prefer = match self.get_header("Prefer") {
+            Some(prefer) => prefer,
+            None => return HashMap::new(),
+        };
+
+        prefer
+            .split(',')
+            .filter_map(|preference| {
+                let preference = preference.split(';').next()?.trim();
+
+                let (name, value) = preference.split_once('=').unwrap_or((preference, ""));
+                let name = name.trim().to_lowercase();
+
+                match name.is_empty() {
+                    true => None,
+                    false => Some((name, value.trim().trim_matches('"').to_string())),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the value of a cookie set with [crate::response::Response::set_signed_cookie], None if the cookie doesn't exist or its signature isn't valid for the key and the name of the cookie.
+    #[cfg(feature = "signed-cookies")]
+    pub fn signed_cookie(&self, name: &str, key: &[u8]) -> Option<String> {
+        crate::signing::verify(name, self.cookies.get(name)?, key)
+    }
+
+    /// Returns true if the client used https to connect with the proxy, using the `X-Forwarded-Proto` or `Forwarded` headers. If `trust_proxy` is false, the headers are ignored and it returns false.
+    pub fn is_secure(&self, trust_proxy: bool) -> bool {
+        if !trust_proxy {
+            return false;
+        }
+
+        if let Some(proto) = self.get_header("X-Forwarded-Proto") {
+            let first_proto = proto.split(',').next().unwrap_or("").trim();
+
+            return first_proto.eq_ignore_ascii_case("https");
+        }
+
+        if let Some(forwarded) = self.get_header("Forwarded") {
+            let first_element = forwarded.split(',').next().unwrap_or("");
+
+            return first_element.split(';').any(|pair| {
+                let mut pair_parts = pair.splitn(2, '=');
+                let key = pair_parts.next().unwrap_or("").trim();
+                let value = pair_parts.next().unwrap_or("").trim().trim_matches('"');
+
+                key.eq_ignore_ascii_case("proto") && value.eq_ignore_ascii_case("https")
+            });
+        }
+
+        false
+    }
+
+    /// Returns the origin of the request from the `Origin` header, or from the `Referer` header if there is no `Origin` header. Returns None if neither can be parsed.
+    pub fn origin(&self) -> Option<Origin> {
+        let origin_str = self
+            .get_header("Origin")
+            .or_else(|| self.get_header("Referer"))?;
+
+        Origin::try_from(origin_str.as_str()).ok()
+    }
+
+    /// Returns the `Referer` header split into its components. Returns None if there is no `Referer` header or it's malformed.
+    pub fn referer(&self) -> Option<Url> {
+        let referer = self.get_header("Referer")?;
+
+        Url::try_from(referer.as_str()).ok()
+    }
+
+    /// Returns the entity tags of the `If-Match` header. Returns None if there is no `If-Match` header or it's malformed.
+    pub fn if_match(&self) -> Option<Vec<ETag>> {
+        let if_match = self.get_header("If-Match")?;
+
+        ETag::parse_list(&if_match).ok()
+    }
+
+    /// Returns true if the `If-Match` precondition passes for the current entity tag of the resource (None if the resource doesn't exist). Requests without `If-Match` always pass, if it fails the request should be answered with [crate::response::Response::precondition_failed].
+    pub fn if_match_passes(&self, current: Option<&ETag>) -> bool {
+        if self.get_header("If-Match").is_none() {
+            return true;
+        }
+
+        let current = match current {
+            Some(current) => current,
+            None => return false,
+        };
+
+        self.if_match()
+            .unwrap_or_default()
+            .iter()
+            .any(|etag| etag.strong_eq(current))
+    }
+
+    /// Returns true if the request has any precondition header (`If-Match`, `If-None-Match`, `If-Modified-Since`, `If-Unmodified-Since` or `If-Range`), so its response depends on the state of the resource.
+    pub fn is_conditional(&self) -> bool {
+        [
+            "If-Match",
+            "If-None-Match",
+            "If-Modified-Since",
+            "If-Unmodified-Since",
+            "If-Range",
+        ]
+        .iter()
+        .any(|header| self.get_header(header).is_some())
+    }
+
+    /// Returns the date of the `If-Unmodified-Since` header. Returns None if there is no `If-Unmodified-Since` header or it isn't a valid HTTP date, the header must be ignored in that case. If the resource was modified after the date the request should be answered with [crate::response::Response::precondition_failed].
+    pub fn if_unmodified_since(&self) -> Option<SystemTime> {
+        let if_unmodified_since = self.get_header("If-Unmodified-Since")?;
+
+        parse_http_date(&if_unmodified_since)
+    }
+
+    /// Returns the credentials of the `Authorization` header. Returns None if there is no `Authorization` header or it's malformed.
+    pub fn auth(&self) -> Option<Auth> {
+        let authorization = self.get_header("Authorization")?;
+
+        Auth::try_from(authorization.as_str()).ok()
+    }
+
+    /// Returns the `Range` header of the request. Returns None if there is no `Range` header or it's malformed.
+    pub fn range(&self) -> Option<Range> {
+        let range = self.get_header("Range")?;
+
+        Range::try_from(range.as_str()).ok()
+    }
+
+    /// Returns the transfer codings accepted by the client in the `TE` header with their quality weights, including the `trailers` token.
+    pub fn accepted_transfer_codings(&self) -> Vec<(String, f32)> {
+        quality::parse_quality_list(&self.header_list("TE"))
+    }
+
+    /// Returns true if the client accepts trailer fields in chunked responses (`TE: trailers`).
+    pub fn accepts_trailers(&self) -> bool {
+        self.accepted_transfer_codings()
+            .iter()
+            .any(|(coding, _)| coding == "trailers")
+    }
+
+    /// Returns the WebSocket subprotocols offered by the client in the `Sec-WebSocket-Protocol` header, in order of preference. Check [crate::response::Response::set_websocket_protocol] to accept one of them.
+    pub fn websocket_subprotocols(&self) -> Vec<String> {
+        self.header_list("Sec-WebSocket-Protocol")
+    }
+
+    /// Returns the content coding of the offered ones that the client prefers according to the `Accept-Encoding` header, the first offered coding wins on ties. `*` matches the codings not listed, and `identity` is accepted unless it's excluded with `identity;q=0` or `*;q=0`. Returns None if no offered coding is acceptable.
+    pub fn preferred_encoding<'a>(&self, offered: &[&'a str]) -> Option<&'a str> {
+        if self.get_header("Accept-Encoding").is_none() {
+            return offered.first().copied();
+        }
+
+        let accepted = quality::parse_quality_list(&self.header_list("Accept-Encoding"));
+
+        let weight_of = |coding: &str| {
+            let listed_weight = |name: &str| {
+                accepted
+                    .iter()
+                    .find(|(accepted_coding, _)| accepted_coding == name)
+                    .map(|(_, weight)| *weight)
+            };
+
+            listed_weight(&coding.to_lowercase())
+                .or_else(|| listed_weight("*"))
+                .unwrap_or(match coding.eq_ignore_ascii_case("identity") {
+                    true => 1.0,
+                    false => 0.0,
+                })
+        };
+
+        offered
+            .iter()
+            .map(|coding| (*coding, weight_of(coding)))
+            .filter(|(_, weight)| *weight > 0.0)
+            .fold(
+                None,
+                |best: Option<(&str, f32)>, (coding, weight)| match best {
+                    Some((_, best_weight)) if best_weight >= weight => best,
+                    _ => Some((coding, weight)),
+                },
+            )
+            .map(|(coding, _)| coding)
+    }
+
+    /// Returns true if the client accepts the media type (Like `application/json`) according to the `Accept` header. The most specific matching range decides (`application/json` over `application/*` over `*/*`), so a media type excluded with `q=0` isn't accepted even if a wildcard matches it. Requests without `Accept` header accept any media type.
+    pub fn accepts(&self, media_type: &str) -> bool {
+        if self.get_header("Accept").is_none() {
+            return true;
+        }
+
+        let accepted = quality::parse_quality_list(&self.header_list("Accept"));
+
+        let media_type = media_type
+            .split(';')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .to_lowercase();
+        let type_range = match media_type.split_once('/') {
+            Some((main_type, _)) => format!("{}/*", main_type),
+            None => return false,
+        };
+
+        let listed_weight = |range: &str| {
+            accepted
+                .iter()
+                .find(|(accepted_range, _)| accepted_range == range)
+                .map(|(_, weight)| *weight)
+        };
+
+        listed_weight(&media_type)
+            .or_else(|| listed_weight(&type_range))
+            .or_else(|| listed_weight("*/*"))
+            .is_some_and(|weight| weight > 0.0)
+    }
+
+    /// Returns the urlencoded form of the body so it can be modified, the body is parsed the first time it's called. Call [Request::sync_form_to_body] to write back the changes.
+    pub fn form_mut(&mut self) -> Result<&mut Query, crate::Error> {
+        if self.form.0.is_none() {
+            let body = self.get_body_string();
+
+            let form = match body.is_empty() {
+                true => Query::new(),
+                false => Query::try_from(body.as_str())?,
+            };
+
+            self.form = FormCache(Some(form));
+        }
+
+        Ok(self.form.0.get_or_insert_with(Query::new))
+    }
+
+    /// Encodes the form returned by [Request::form_mut] back into the body, updating the `Content-Length` header.
+    pub fn sync_form_to_body(&mut self) {
+        if let Some(form) = self.form.0.take() {
+            let body = form.to_string().into_bytes();
+
+            self.add_header("Content-Length", &body.len().to_string());
+            self.set_body(body);
+            self.form = FormCache(Some(form));
+        }
+    }
+
+    fn parse_header_str(
+        header_string: &str,
+        config: &ParseConfig,
+    ) -> Result<Request, crate::Error> {
         let mut lines = header_string.lines();
 
         let mut request = match lines.next() {
+            // Whitespace before the method can be used to hide request lines from other parsers, only lenient parsing ignores it
+            Some(request_line)
+                if config.strict && request_line.starts_with(char::is_whitespace) =>
+            {
+                return Err(crate::Error::RequestError(RequestError::InvalidRequest(
+                    String::from(request_line),
+                )))
+            }
             Some(request_line) => {
                 let mut request_line_parts = request_line.split_whitespace();
 
@@ -69,7 +761,7 @@ impl Request {
                     }
                 };
 
-                let request_method = match Method::try_from(request_method_string) {
+                let request_method = match Method::parse(request_method_string, config) {
                     Ok(method) => method,
                     Err(_) => {
                         return Err(crate::Error::RequestError(
@@ -83,6 +775,13 @@ impl Request {
                     None => return Err(crate::Error::RequestError(RequestError::NoUrlFound)),
                 };
 
+                // The asterisk-form target (`OPTIONS * HTTP/1.1`) is only valid for server-wide OPTIONS requests
+                if request_path_with_query == "*" && request_method != Method::OPTIONS {
+                    return Err(crate::Error::RequestError(RequestError::InvalidRequest(
+                        String::from(request_line),
+                    )));
+                }
+
                 let (request_path, query) = match request_path_with_query.contains('?') {
                     true => {
                         let mut url_and_query = request_path_with_query.splitn(2, '?');
@@ -103,15 +802,17 @@ impl Request {
                             }
                         };
 
-                        let query = Query::try_from(query_string)?;
+                        let query = Query::parse(query_string, config)?;
 
                         (request_path, Some(query))
                     }
                     false => (request_path_with_query, None),
                 };
 
+                // Lenient parsing accepts request lines without version (`GET /`), used by some health checkers
                 let http_version = match request_line_parts.next() {
                     Some(version) => version,
+                    None if !config.strict => "HTTP/1.0",
                     None => {
                         return Err(crate::Error::RequestError(RequestError::InvalidRequest(
                             String::from(header_string),
@@ -125,7 +826,16 @@ impl Request {
                     ));
                 }
 
-                Request::new(request_method, request_path, query)
+                let decoded_path = query::decode_path(request_path);
+
+                // The raw path is only kept if decoding changed it, so requests whose path is equal still compare equal
+                let raw_path = (decoded_path != request_path).then(|| String::from(request_path));
+
+                let mut request = Request::new(request_method, &decoded_path, query);
+                request.raw_path = raw_path;
+                request.version = HttpVersion::from(http_version);
+
+                request
             }
             None => {
                 return Err(crate::Error::RequestError(RequestError::InvalidRequest(
@@ -134,15 +844,28 @@ impl Request {
             }
         };
 
-        for header in lines.by_ref() {
+        let mut host_count = 0;
+
+        for (index, header) in lines.by_ref().enumerate() {
             if header.is_empty() {
                 break;
             }
 
+            if index >= config.max_headers {
+                return Err(crate::Error::RequestError(RequestError::TooManyHeaders(
+                    config.max_headers,
+                )));
+            }
+
             let mut header_parts = header.splitn(2, ':');
 
             let header_key = match header_parts.next() {
-                Some(key) => key,
+                Some(key) if key != key.trim_end() && config.strict => {
+                    return Err(crate::Error::RequestError(RequestError::InvalidHeader(
+                        String::from(header),
+                    )))
+                }
+                Some(key) => key.trim_end(),
                 None => {
                     return Err(crate::Error::RequestError(RequestError::InvalidHeader(
                         String::from(header),
@@ -159,12 +882,49 @@ impl Request {
                 }
             };
 
+            // Duplicated Content-Length headers are only allowed if every value is the same (RFC 7230 §3.3.2)
+            if header_key.eq_ignore_ascii_case("Content-Length") {
+                if let Some(content_length) = request.get_header("Content-Length") {
+                    if content_length != header_value {
+                        return Err(crate::Error::RequestError(
+                            RequestError::ConflictingFraming(format!(
+                                "Content-Length: {} and Content-Length: {}",
+                                content_length, header_value
+                            )),
+                        ));
+                    }
+                }
+            }
+
+            // Several Host headers can make the router and a cache disagree on the target of the request
+            if header_key.eq_ignore_ascii_case("Host") {
+                host_count += 1;
+
+                if host_count > 1 && config.strict {
+                    return Err(crate::Error::RequestError(
+                        RequestError::MultipleHostHeaders(host_count),
+                    ));
+                }
+            }
+
             request.add_header(header_key, header_value);
         }
 
+        // A request with both a Content-Length and a chunked body can be used for request smuggling (RFC 7230 §3.3.3)
+        if let (true, Some(content_length)) =
+            (request.is_chunked(), request.get_header("Content-Length"))
+        {
+            return Err(crate::Error::RequestError(
+                RequestError::ConflictingFraming(format!(
+                    "Content-Length: {} with a chunked Transfer-Encoding",
+                    content_length
+                )),
+            ));
+        }
+
         let _a = request.get_header_list().get("Cookie");
         if let Some(cookies) = request.get_header_list().get("Cookie") {
-            let cookie_list = CookieList::try_from(cookies.as_str())?;
+            let cookie_list = CookieList::parse(cookies, config)?;
 
             request.cookies = cookie_list;
         }
@@ -173,6 +933,46 @@ impl Request {
     }
 }
 
+impl Request {
+    /// Serializes the request to send it to a server, percent-encoding the path with the given set (Check [EncodeSet]). Parsed requests whose path wasn't changed are sent with the raw path they were received with (Check [Request::raw_path]), so decoded sequences like `%2541` aren't decoded twice. The headers and body are sent as they are, no `Content-Length` is added.
+    pub fn serialize_with(&self, path_encode_set: EncodeSet) -> Vec<u8> {
+        let path = match &self.raw_path {
+            Some(raw_path) if query::decode_path(raw_path) == self.path.path => raw_path.clone(),
+            _ => path_encode_set.encode(&self.path.path),
+        };
+
+        let mut request_line = format!("{} {}", self.path.method, path);
+
+        if let Some(query) = self.query.as_ref().filter(|query| !query.is_empty()) {
+            request_line.push_str(&format!("?{}", query));
+        }
+
+        let mut head = format!("{} {}\r\n", request_line, self.version);
+
+        for (key, value) in &self.headers {
+            for line_value in value.split('\n') {
+                head.push_str(&format!("{}: {}\r\n", key, line_value));
+            }
+        }
+
+        head.push_str("\r\n");
+
+        let mut request = head.into_bytes();
+
+        if let Some(body) = &self.body {
+            request.extend_from_slice(body);
+        }
+
+        request
+    }
+}
+
+impl BinaryRepresentation for Request {
+    fn to_binary(&self) -> Vec<u8> {
+        self.serialize_with(EncodeSet::Path)
+    }
+}
+
 impl From<Route> for Request {
     fn from(path: Route) -> Self {
         Request::new(path.method, path.path.as_str(), None)
@@ -183,45 +983,17 @@ impl TryFrom<&str> for Request {
     type Error = crate::Error;
 
     fn try_from(req: &str) -> Result<Self, Self::Error> {
-        let request = Request::parse_header_str(req)?;
+        let request = Request::parse_header_str(req, &ParseConfig::default())?;
 
         Ok(request)
     }
 }
 
-macro_rules! split_sequence {
-    ($sequence:expr, $($separator:expr),*) => {{
-        let mut header = Vec::new();
-        let mut body = $sequence.to_vec();
-
-        $(
-            if let Some(pos) = body
-                .windows($separator.len())
-                .position(|window| window == $separator)
-            {
-                let (header_reference, body_reference) = body.split_at(pos);
-                header = header_reference.to_vec();
-                body = body_reference[$separator.len()..].to_vec();
-            }
-        )*
-
-        (header, body)
-    }};
-}
-
 impl TryFrom<Vec<u8>> for Request {
     type Error = crate::Error;
 
     fn try_from(binary_data: Vec<u8>) -> Result<Self, Self::Error> {
-        let (header, body) = split_sequence!(binary_data, b"\r\n\r\n", b"\n\n");
-
-        let header_string = String::from_utf8_lossy(&header);
-
-        let mut request = Request::parse_header_str(header_string.as_ref())?;
-
-        request.set_body(body);
-
-        Ok(request)
+        Request::parse_with_config(&binary_data, &ParseConfig::default())
     }
 }
 
@@ -256,4 +1028,56 @@ pub enum RequestError {
     /// Error while parsing cookies
     #[error("Error parsing cookies: {0}")]
     CookieError(String),
+
+    /// Error while parsing the origin of the request
+    #[error("Error parsing origin: {0}")]
+    OriginError(String),
+
+    /// Error while parsing an URL
+    #[error("Error parsing URL: {0}")]
+    UrlError(String),
+
+    /// Error while parsing entity tags
+    #[error("Error parsing entity tag: {0}")]
+    ETagError(String),
+
+    /// The body is bigger than the allowed limit, check [crate::request::ParseConfig].
+    #[error("Request body too large, the limit is {0} bytes")]
+    BodyTooLarge(usize),
+
+    /// Error while parsing the `Range` header
+    #[error("Error parsing range: {0}")]
+    RangeError(String),
+
+    /// A chunk of a chunked body is malformed.
+    #[error("Invalid chunk: {0}")]
+    InvalidChunk(String),
+
+    /// A line is longer than the allowed limit, check [crate::request::ParseConfig].
+    #[error("Line too long, the limit is {0} bytes")]
+    LineTooLong(usize),
+
+    /// The request declares its body length in conflicting ways, like a `Content-Length` with a chunked `Transfer-Encoding`.
+    #[error("Conflicting message framing: {0}")]
+    ConflictingFraming(String),
+
+    /// The `Content-Length` header isn't a single number made only of digits.
+    #[error("Invalid Content-Length: {0}")]
+    InvalidContentLength(String),
+
+    /// The request has more header lines than the allowed limit, check [crate::request::ParseConfig].
+    #[error("Too many headers, the limit is {0}")]
+    TooManyHeaders(usize),
+
+    /// The request line and headers are bigger than the allowed limit, check [crate::request::ParseConfig].
+    #[error("Request header too large, the limit is {0} bytes")]
+    HeaderTooLarge(usize),
+
+    /// Error while parsing the `Authorization` header
+    #[error("Error parsing authorization: {0}")]
+    AuthError(String),
+
+    /// The request has more than one `Host` header, only rejected by strict parsing. Contains the number of `Host` headers found so far.
+    #[error("Multiple Host headers: {0}")]
+    MultipleHostHeaders(usize),
 }
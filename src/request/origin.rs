@@ -0,0 +1,97 @@
+use super::RequestError;
+
+/// Origin of a request, parsed from the `Origin` (or `Referer`) header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// The `null` origin, sent by privacy-sensitive contexts (sandboxed iframes, local files...).
+    Null,
+
+    /// An origin made of a scheme, a host and an optional port.
+    Tuple {
+        /// Scheme of the origin, like `https`.
+        scheme: String,
+
+        /// Host of the origin, like `app.example.com`.
+        host: String,
+
+        /// Port of the origin, if it was explicitly set.
+        port: Option<u16>,
+    },
+}
+
+impl TryFrom<&str> for Origin {
+    type Error = crate::Error;
+
+    fn try_from(origin_str: &str) -> Result<Self, Self::Error> {
+        let origin_error =
+            || crate::Error::RequestError(RequestError::OriginError(String::from(origin_str)));
+
+        let trimmed_origin_str = origin_str.trim();
+
+        if trimmed_origin_str == "null" {
+            return Ok(Origin::Null);
+        }
+
+        let (scheme, authority) = trimmed_origin_str
+            .split_once("://")
+            .ok_or_else(origin_error)?;
+
+        // Referer values can contain a path, it isn't part of the origin
+        let authority = authority.split(['/', '?', '#']).next().unwrap_or("");
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => {
+                (host, Some(port.parse::<u16>().map_err(|_| origin_error())?))
+            }
+            _ => (authority, None),
+        };
+
+        if scheme.is_empty() || host.is_empty() {
+            return Err(origin_error());
+        }
+
+        Ok(Origin::Tuple {
+            scheme: scheme.to_lowercase(),
+            host: host.to_lowercase(),
+            port,
+        })
+    }
+}
+
+impl Origin {
+    /// Returns the port of the origin, using the default port of the scheme if it wasn't explicitly set.
+    pub fn effective_port(&self) -> Option<u16> {
+        match self {
+            Origin::Null => None,
+            Origin::Tuple { scheme, port, .. } => port.or(match scheme.as_str() {
+                "http" | "ws" => Some(80),
+                "https" | "wss" => Some(443),
+                _ => None,
+            }),
+        }
+    }
+
+    /// Returns true if the origin is the same as the given origin string. The `null` origin never matches.
+    pub fn matches(&self, other: &str) -> bool {
+        let other = match Origin::try_from(other) {
+            Ok(origin) => origin,
+            Err(_) => return false,
+        };
+
+        match (self, &other) {
+            (
+                Origin::Tuple { scheme, host, .. },
+                Origin::Tuple {
+                    scheme: other_scheme,
+                    host: other_host,
+                    ..
+                },
+            ) => {
+                scheme == other_scheme
+                    && host == other_host
+                    && self.effective_port() == other.effective_port()
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+/// Options used when parsing a request, check [crate::request::Request::parse_with_config].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseConfig {
+    /// Rejects requests that don't strictly follow the RFC instead of trying to recover from the errors.
+    pub strict: bool,
+
+    /// Maximum size of the body in bytes.
+    pub max_body_size: usize,
+
+    /// Maximum size in bytes of a single chunk of a chunked body.
+    pub max_chunk_size: usize,
+
+    /// Maximum length in bytes of a single line read from a stream, like the size line of a chunk or a trailer.
+    pub max_line_length: usize,
+
+    /// Maximum number of parameters of a query string.
+    pub max_query_params: usize,
+
+    /// Maximum number of header lines of a request.
+    pub max_headers: usize,
+
+    /// Maximum size in bytes of the request line and headers read from a stream, including the line breaks.
+    pub max_header_size: usize,
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        Self::lenient()
+    }
+}
+
+impl ParseConfig {
+    /// Generates a config that tries to recover from common client mistakes. Used by default.
+    pub fn lenient() -> Self {
+        ParseConfig {
+            strict: false,
+            max_body_size: 8 * 1024 * 1024,
+            max_chunk_size: 1024 * 1024,
+            max_line_length: 8 * 1024,
+            max_query_params: 256,
+            max_headers: 100,
+            max_header_size: 64 * 1024,
+        }
+    }
+
+    /// Generates a config that rejects any request that doesn't strictly follow the RFC.
+    pub fn strict() -> Self {
+        ParseConfig {
+            strict: true,
+            ..Self::lenient()
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use super::Request;
+
+/// Result of parsing a request from a buffer that may not have received the whole request yet, check [Request::parse_prefix].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseStatus {
+    /// The buffer has a whole request, with the number of bytes it used. The bytes after it belong to the next request.
+    Complete(Box<Request>, usize),
+
+    /// The buffer doesn't have the whole request yet, with the number of bytes still needed if it's known (Only when the header is complete and the body length comes from `Content-Length`).
+    Incomplete(Option<usize>),
+}
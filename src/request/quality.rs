@@ -0,0 +1,30 @@
+/// Parses the items of a comma separated header (Check [crate::package::Package::header_list]) with optional quality weights (`gzip`, `br;q=0.9`). Values are lowercased, values without weight get a weight of `1.0`, values with a malformed weight are skipped.
+pub(crate) fn parse_quality_list(items: &[String]) -> Vec<(String, f32)> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let mut parameters = item.split(';');
+
+            let value = parameters.next()?.trim().to_lowercase();
+
+            if value.is_empty() {
+                return None;
+            }
+
+            let mut quality = 1.0;
+
+            for parameter in parameters {
+                if let Some((key, weight)) = parameter.split_once('=') {
+                    if key.trim().eq_ignore_ascii_case("q") {
+                        quality = weight.trim().parse::<f32>().ok()?;
+                    }
+                }
+            }
+
+            match (0.0..=1.0).contains(&quality) {
+                true => Some((value, quality)),
+                false => None,
+            }
+        })
+        .collect()
+}
@@ -1,20 +1,43 @@
-use std::collections::HashMap;
+use std::collections::{hash_map, HashMap};
+use std::fmt::Display;
 
-use crate::request::RequestError;
+use crate::request::{ParseConfig, RequestError};
 use crate::Error;
 
-/// Query parameters of a request, represented as a key-value pair.
+/// Query parameters of a request, represented as a key-value pair. Keys without value are stored with an empty value, check [Query::has_flag].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Query {
     query: HashMap<String, String>,
 }
 
+impl Default for Query {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Query {
+    /// Generates a new empty query.
+    pub fn new() -> Self {
+        Query {
+            query: HashMap::new(),
+        }
+    }
+
     /// Returns an Ok(String) with the parameter if the value is found, else it returns None.
     pub fn get(&self, key: &str) -> Option<&String> {
         self.query.get(key)
     }
 
+    /// Returns the parameter as a boolean, `true`, `1`, `on` and `yes` are true and `false`, `0`, `off` and `no` are false (Ignoring case). Returns None if the key isn't found or the value isn't recognized.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)?.trim().to_lowercase().as_str() {
+            "true" | "1" | "on" | "yes" => Some(true),
+            "false" | "0" | "off" | "no" => Some(false),
+            _ => None,
+        }
+    }
+
     /// Sets a new key-value pair in the query. Returns the previous value if the key already exists.
     pub fn set(&mut self, key: &str, value: &str) -> Option<String> {
         self.query.insert(String::from(key), String::from(value))
@@ -25,71 +48,148 @@ impl Query {
         self.query.remove(key)
     }
 
+    /// Returns true if the key appears in the query, with or without value. Keys without value (`verbose` in `?verbose&page=2`) are stored with an empty value, so [Query::get] returns `Some("")` for them and can't tell them apart from `verbose=`, use this method to check flags like these.
+    pub fn has_flag(&self, key: &str) -> bool {
+        self.contains(key)
+    }
+
     /// Returns true if the query contains the key.
     pub fn contains(&self, key: &str) -> bool {
         self.query.contains_key(key)
     }
+
+    /// Adds the parameters of the defaults that aren't already in the query. The values of the query always win, a default never replaces or duplicates a key that is already set.
+    pub fn merge(&mut self, defaults: &Query) {
+        for (key, value) in defaults {
+            if !self.contains(key) {
+                self.set(key, value);
+            }
+        }
+    }
+
+    /// Returns true if the query has no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty()
+    }
+
+    /// Returns the number of parameters of the query.
+    pub fn len(&self) -> usize {
+        self.query.len()
+    }
+
+    /// Returns an iterator over the key-value pairs of the query, in arbitrary order.
+    pub fn iter(&self) -> QueryIter<'_> {
+        QueryIter {
+            inner: self.query.iter(),
+        }
+    }
+}
+
+/// Encodes the query as an urlencoded string (`key=value&key2=value2`), spaces are encoded as `+`.
+impl Display for Query {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pairs: Vec<String> = self
+            .query
+            .iter()
+            .map(|(key, value)| format!("{}={}", encode_component(key), encode_component(value)))
+            .collect();
+
+        write!(f, "{}", pairs.join("&"))
+    }
 }
 
-macro_rules! parse_query_string {
-    ($query_string:expr; $($replaced:expr => $replacement:expr),*) => {{
-        let mut new_query_string = String::from($query_string);
+fn encode_component(component: &str) -> String {
+    component
+        .bytes()
+        .map(|byte| match byte {
+            b' ' => String::from("+"),
+            b if b.is_ascii_alphanumeric() || b"-._~".contains(&b) => (b as char).to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
 
-        $(
-            new_query_string = new_query_string.replace($replaced, $replacement);
-        )*
+/// Iterator over the key-value pairs of a [Query], check [Query::iter].
+#[derive(Debug, Clone)]
+pub struct QueryIter<'a> {
+    inner: hash_map::Iter<'a, String, String>,
+}
+
+impl<'a> Iterator for QueryIter<'a> {
+    type Item = (&'a str, &'a str);
 
-        new_query_string
-    }};
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+impl<'a> IntoIterator for &'a Query {
+    type Item = (&'a str, &'a str);
+    type IntoIter = QueryIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// How the components of a query are decoded, check [Query::try_from_with].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueryDecodeMode {
+    /// Decodes the query like an urlencoded form, `+` is decoded as a space. Used by default.
+    #[default]
+    Form,
+
+    /// Only decodes percent-encoded characters (RFC 3986), `+` is kept as is. Used when `+` is meaningful, like in base64 values.
+    Strict,
 }
 
 impl TryFrom<&str> for Query {
     type Error = Error;
 
     fn try_from(query_string: &str) -> Result<Self, Self::Error> {
+        Query::parse(query_string, &ParseConfig::default())
+    }
+}
+
+impl Query {
+    /// Parses a query string using the given parsing options, returns a [RequestError::QueryError] as soon as the query has more than [ParseConfig::max_query_params] parameters. The components are decoded with [QueryDecodeMode::Form].
+    pub fn parse(query_string: &str, config: &ParseConfig) -> Result<Self, Error> {
+        Query::parse_with_mode(query_string, config, QueryDecodeMode::Form)
+    }
+
+    /// Parses a query string decoding its components with the given mode.
+    pub fn try_from_with(query_string: &str, mode: QueryDecodeMode) -> Result<Self, Error> {
+        Query::parse_with_mode(query_string, &ParseConfig::default(), mode)
+    }
+
+    fn parse_with_mode(
+        query_string: &str,
+        config: &ParseConfig,
+        mode: QueryDecodeMode,
+    ) -> Result<Self, Error> {
         let mut query_map = HashMap::new();
 
         let trimmed_query_string = query_string.trim_start_matches('?');
 
-        let parsed_query_string = parse_query_string!(
-            trimmed_query_string;
-            "%20" => " ",
-            "%21" => "!",
-            "%23" => "#",
-            "%24" => "$",
-            "%26" => "&",
-            "%27" => "'",
-            "%28" => "(",
-            "%29" => ")",
-            "%2A" => "*",
-            "%2B" => "+",
-            "%2C" => ",",
-            "%2F" => "/",
-            "%3A" => ":",
-            "%3B" => ";",
-            "%3D" => "=",
-            "%3F" => "?",
-            "%40" => "@",
-            "%5B" => "[",
-            "%5D" => "]",
-            "+" => " "
-        );
-
-        for query_pair in parsed_query_string.split('&') {
-            let mut query_pair = query_pair.split('=');
-
-            let key = query_pair
-                .next()
-                .ok_or(Error::RequestError(RequestError::QueryError(String::from(
-                    query_string,
-                ))))?;
-            let value = query_pair
-                .next()
-                .ok_or(Error::RequestError(RequestError::QueryError(String::from(
-                    query_string,
-                ))))?;
-
-            query_map.insert(String::from(key), String::from(value));
+        // The pairs are split before decoding them, so encoded `&` and `=` are kept inside the keys and values
+        for (index, query_pair) in trimmed_query_string.split('&').enumerate() {
+            if index >= config.max_query_params {
+                return Err(Error::RequestError(RequestError::QueryError(format!(
+                    "More than {} parameters",
+                    config.max_query_params
+                ))));
+            }
+
+            if query_pair.is_empty() {
+                continue;
+            }
+
+            // Keys without value (`?verbose`) are kept with an empty value
+            let (key, value) = query_pair.split_once('=').unwrap_or((query_pair, ""));
+
+            query_map.insert(decode_component(key, mode), decode_component(value, mode));
         }
 
         let query = Query { query: query_map };
@@ -97,3 +197,45 @@ impl TryFrom<&str> for Query {
         Ok(query)
     }
 }
+
+fn decode_component(component: &str, mode: QueryDecodeMode) -> String {
+    percent_decode(component, mode == QueryDecodeMode::Form, |_| false)
+}
+
+/// Decodes the percent-encoded characters of a request path, except encoded slashes (`%2F`) which would change the segments of the path and control characters (`%00`, `%0A`...) which aren't valid in a route path.
+pub(crate) fn decode_path(path: &str) -> String {
+    percent_decode(path, false, |byte| byte == b'/' || byte.is_ascii_control())
+}
+
+// Malformed percent-encoded sequences are kept as they are, like the sequences of the bytes that must be kept encoded
+fn percent_decode(component: &str, plus_as_space: bool, keep_encoded: fn(u8) -> bool) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let escaped_byte = match bytes[index] {
+            b'%' => bytes
+                .get(index + 1..index + 3)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .filter(|byte| !keep_encoded(*byte)),
+            _ => None,
+        };
+
+        match (bytes[index], escaped_byte) {
+            (_, Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+            (b'+', None) if plus_as_space => decoded.push(b' '),
+            (byte, None) => decoded.push(byte),
+        }
+
+        index += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
@@ -0,0 +1,91 @@
+use super::RequestError;
+
+/// Value of the `Range` header, made of a unit and the ranges requested in that unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range {
+    /// Unit of the ranges, only `bytes` ranges are supported by [Range::byte_ranges].
+    pub unit: String,
+
+    ranges: String,
+}
+
+/// A single range of bytes of a `bytes` [Range].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// From the first position to the last one (both included), like `0-9`.
+    FromTo(u64, u64),
+
+    /// From the position to the end, like `10-`.
+    From(u64),
+
+    /// The last bytes, like `-10`.
+    Suffix(u64),
+}
+
+impl TryFrom<&str> for Range {
+    type Error = crate::Error;
+
+    fn try_from(range_str: &str) -> Result<Self, Self::Error> {
+        let (unit, ranges) = range_str.trim().split_once('=').ok_or_else(|| {
+            crate::Error::RequestError(RequestError::RangeError(String::from(range_str)))
+        })?;
+
+        if unit.trim().is_empty() {
+            return Err(crate::Error::RequestError(RequestError::RangeError(
+                String::from(range_str),
+            )));
+        }
+
+        Ok(Range {
+            unit: unit.trim().to_lowercase(),
+            ranges: String::from(ranges.trim()),
+        })
+    }
+}
+
+impl Range {
+    /// Returns the requested byte ranges. Returns None if the unit isn't `bytes` or the ranges are malformed, the full content should be served in that case.
+    pub fn byte_ranges(&self) -> Option<Vec<ByteRange>> {
+        if self.unit != "bytes" {
+            return None;
+        }
+
+        self.ranges
+            .split(',')
+            .map(|range| {
+                let (start, end) = range.trim().split_once('-')?;
+
+                match (start.trim(), end.trim()) {
+                    ("", "") => None,
+                    ("", suffix) => Some(ByteRange::Suffix(suffix.parse().ok()?)),
+                    (start, "") => Some(ByteRange::From(start.parse().ok()?)),
+                    (start, end) => {
+                        let (start, end) = (start.parse().ok()?, end.parse().ok()?);
+
+                        match start <= end {
+                            true => Some(ByteRange::FromTo(start, end)),
+                            false => None,
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+impl ByteRange {
+    /// Returns the first and last positions (both included) of the range for a content of the given length. Returns None if the range can't be satisfied.
+    pub fn resolve(&self, len: u64) -> Option<(u64, u64)> {
+        if len == 0 {
+            return None;
+        }
+
+        match *self {
+            ByteRange::FromTo(start, _) | ByteRange::From(start) if start >= len => None,
+            ByteRange::FromTo(start, end) => Some((start, end.min(len - 1))),
+            ByteRange::From(start) => Some((start, len - 1)),
+            ByteRange::Suffix(0) => None,
+            ByteRange::Suffix(suffix) => Some((len.saturating_sub(suffix), len - 1)),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use super::ParseConfig;
+
+/// Options used when reading a request from a stream, check [crate::request::Request::read_from].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReaderConfig {
+    /// Sends a `100 Continue` interim response before reading the body of requests with the `Expect: 100-continue` header.
+    pub auto_continue: bool,
+
+    /// Options used to parse the request.
+    pub parse_config: ParseConfig,
+}
+
+impl Default for ReaderConfig {
+    fn default() -> Self {
+        ReaderConfig {
+            auto_continue: true,
+            parse_config: ParseConfig::default(),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use super::RequestError;
+
+/// Minimal representation of an URL, split into its components. Can be either absolute (`https://example.com/path?query`) or path only (`/path?query`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Url {
+    /// Scheme of the URL, None for path only URLs.
+    pub scheme: Option<String>,
+
+    /// Host of the URL (With the port if it has one), None for path only URLs.
+    pub host: Option<String>,
+
+    /// Path of the URL, `/` if it's empty.
+    pub path: String,
+
+    /// Query of the URL, without the `?`.
+    pub query: Option<String>,
+}
+
+impl TryFrom<&str> for Url {
+    type Error = crate::Error;
+
+    fn try_from(url_str: &str) -> Result<Self, Self::Error> {
+        let url_error =
+            || crate::Error::RequestError(RequestError::UrlError(String::from(url_str)));
+
+        if url_str.is_empty() || url_str.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(url_error());
+        }
+
+        let url_without_fragment = url_str.split('#').next().unwrap_or("");
+
+        let (scheme, host, path_and_query) = match url_without_fragment.split_once("://") {
+            Some((scheme, rest)) => {
+                let (host, path_and_query) = match rest.find(['/', '?']) {
+                    Some(pos) => rest.split_at(pos),
+                    None => (rest, ""),
+                };
+
+                if scheme.is_empty()
+                    || host.is_empty()
+                    || !scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+                {
+                    return Err(url_error());
+                }
+
+                (
+                    Some(scheme.to_lowercase()),
+                    Some(String::from(host)),
+                    path_and_query,
+                )
+            }
+            None if url_without_fragment.starts_with('/') => (None, None, url_without_fragment),
+            None => return Err(url_error()),
+        };
+
+        let (path, query) = match path_and_query.split_once('?') {
+            Some((path, query)) => (path, Some(String::from(query))),
+            None => (path_and_query, None),
+        };
+
+        let path = match path.is_empty() {
+            true => String::from("/"),
+            false => String::from(path),
+        };
+
+        Ok(Url {
+            scheme,
+            host,
+            path,
+            query,
+        })
+    }
+}
@@ -0,0 +1,23 @@
+use std::{
+    fs::File,
+    sync::{Arc, Mutex},
+};
+
+/// Open file used as the body of a response, it's streamed to the client instead of being read into memory.
+///
+/// The file is read from `start` every time the response is written, so clones of the response (Which share the open file) always send the same bytes.
+#[derive(Debug, Clone)]
+pub(crate) struct FileBody {
+    pub(crate) file: Arc<Mutex<File>>,
+    pub(crate) start: u64,
+    pub(crate) len: u64,
+}
+
+// Two file bodies are equal only if they share the same open file
+impl PartialEq for FileBody {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.file, &other.file) && self.start == other.start && self.len == other.len
+    }
+}
+
+impl Eq for FileBody {}
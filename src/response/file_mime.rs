@@ -102,4 +102,6 @@ generate_mime_functions!(
     "htm" => "text/html"
 );
 
+// Re-exported so other modules can generate their own mime tables
+#[allow(unused_imports)]
 pub(crate) use generate_mime_functions;
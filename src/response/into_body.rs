@@ -0,0 +1,31 @@
+/// Trait for the types that can be used as the body of a [crate::response::Response].
+pub trait IntoBody {
+    /// Returns the content type used when the response doesn't have one.
+    fn default_content_type(&self) -> &'static str;
+
+    /// Converts the value into the bytes of the body.
+    fn into_body(self) -> Vec<u8>;
+}
+
+macro_rules! generate_into_body {
+    ($($type:ty => $content_type:expr, $into_body:expr);*) => {
+        $(
+            impl IntoBody for $type {
+                fn default_content_type(&self) -> &'static str {
+                    $content_type
+                }
+
+                fn into_body(self) -> Vec<u8> {
+                    $into_body(self)
+                }
+            }
+        )*
+    };
+}
+
+generate_into_body!(
+    String => "text/plain; charset=utf-8", String::into_bytes;
+    &str => "text/plain; charset=utf-8", |body: &str| body.as_bytes().to_vec();
+    Vec<u8> => "application/octet-stream", |body| body;
+    &[u8] => "application/octet-stream", <[u8]>::to_vec
+);
@@ -0,0 +1,45 @@
+use super::{Response, Status};
+
+/// Trait for the types that can be converted into a [Response], so handlers can return the most convenient type.
+pub trait IntoResponse {
+    /// Converts the value into a response.
+    fn into_response(self) -> Response;
+}
+
+impl IntoResponse for Response {
+    fn into_response(self) -> Response {
+        self
+    }
+}
+
+/// Generates an empty response with the status.
+impl IntoResponse for Status {
+    fn into_response(self) -> Response {
+        Response::new(self)
+    }
+}
+
+/// Generates a response with the status and the string as body.
+impl IntoResponse for (Status, String) {
+    fn into_response(self) -> Response {
+        let (status, body) = self;
+
+        let mut response = Response::new(status);
+        response.body(body);
+        response
+    }
+}
+
+/// Converts the value if it's Ok, else converts the error into the response.
+impl<T, E> IntoResponse for Result<T, E>
+where
+    T: IntoResponse,
+    E: Into<Response>,
+{
+    fn into_response(self) -> Response {
+        match self {
+            Ok(value) => value.into_response(),
+            Err(err) => err.into(),
+        }
+    }
+}
@@ -1,14 +1,45 @@
-use std::{collections::HashMap, fmt::Display, path::Path};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use crate::{package, BinaryRepresentation};
+use crate::{
+    date::{format_http_date, parse_http_date},
+    etag::ETag,
+    package,
+    request::{chunked, ByteRange, Method, ParseConfig, Request},
+    version::HttpVersion,
+    BinaryRepresentation,
+};
 
 pub use crate::package::Package;
 
+mod file_body;
 pub(crate) mod file_mime;
+mod into_body;
+mod into_response;
+mod negotiation;
+mod retry_after;
+mod sse;
 mod status;
+mod stream_body;
+mod template;
 
+use crate::response::file_body::FileBody;
 use crate::response::file_mime::*;
+use crate::response::stream_body::StreamBody;
+pub use into_body::IntoBody;
+pub use into_response::IntoResponse;
+pub use negotiation::Negotiation;
+pub use retry_after::RetryAfter;
+pub use sse::SseWriter;
 pub use status::Status;
+pub use template::UnmatchedPlaceholder;
 
 /// Struct responsible for handling the response of a request.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -16,39 +47,691 @@ pub struct Response {
     /// Status of the response
     pub status: Status,
 
+    /// HTTP version of the response
+    pub version: HttpVersion,
+
     headers: HashMap<String, String>,
     body: Option<Vec<u8>>,
+    file_body: Option<FileBody>,
+    stream_body: Option<StreamBody>,
+
+    omit_reason_phrase: bool,
+    head: bool,
 }
 
-package::generate_package_getters_setters!(Response[Vec<u8>]);
+package::generate_package_getters_setters!(Response[Vec<u8>], file_body, stream_body);
 
 impl Response {
     /// Generates a new response with the given status.
     pub fn new(status: Status) -> Self {
         Response {
             status,
+            version: HttpVersion::default(),
             headers: HashMap::new(),
             body: None,
+            file_body: None,
+            stream_body: None,
+            omit_reason_phrase: false,
+            head: false,
+        }
+    }
+
+    /// Omits the reason phrase from the status line when serializing (`HTTP/1.1 200` instead of `HTTP/1.1 200 OK`).
+    pub fn omit_reason_phrase(&mut self, omit: bool) {
+        self.omit_reason_phrase = omit;
+    }
+
+    /// Returns the response to a `HEAD` request equivalent to this one, with the same status and headers (Including the `Content-Length` and `Content-Range` the body would have) but without sending the body.
+    pub fn for_head(&self) -> Response {
+        let mut response = self.clone();
+        response.pack();
+        response.head = true;
+
+        response
+    }
+
+    /// Limits the body to the given byte range, setting the `206 Partial Content` status and the `Content-Range` header. If the range can't be satisfied the body is removed and the status is set to `416 Requested Range Not Satisfiable`.
+    ///
+    /// File bodies (Check [Response::set_file_body]) are limited without reading the file, only the range is sent. Stream bodies are left untouched since their length isn't known, so the full body is sent with the current status.
+    pub fn set_range(&mut self, range: &ByteRange) {
+        if self.stream_body.is_some() {
+            return;
+        }
+
+        let len = match &self.file_body {
+            Some(file_body) => file_body.len,
+            None => self.body.as_ref().map_or(0, |body| body.len() as u64),
+        };
+
+        match range.resolve(len) {
+            Some((start, end)) => {
+                self.status = Status::PartialContent;
+                self.add_header("Content-Range", &format!("bytes {}-{}/{}", start, end, len));
+
+                match self.file_body.as_mut() {
+                    Some(file_body) => {
+                        file_body.start += start;
+                        file_body.len = end - start + 1;
+                    }
+                    None => {
+                        let body = self.body.take().unwrap_or_default();
+                        self.set_body(body[start as usize..=end as usize].to_vec());
+                    }
+                }
+            }
+            None => {
+                self.status = Status::RequestedRangeNotSatisfiable;
+                self.add_header("Content-Range", &format!("bytes */{}", len));
+                self.body = None;
+                self.file_body = None;
+            }
+        }
+    }
+
+    /// Generates the response to a request for the body, serving only the requested range (Check [Response::set_range]) when the request has a `Range` header with a single byte range. If the request has an `If-Range` header that doesn't match the current entity tag (Strong comparison) or the last modification date of the body, the full body is served with `200 OK` instead, since the client's copy is outdated.
+    ///
+    /// The `ETag` and `Last-Modified` headers are set from the given validators and the `Accept-Ranges: bytes` header is always set.
+    pub fn range_or_full(
+        body: Vec<u8>,
+        request: &Request,
+        etag: Option<&ETag>,
+        last_modified: Option<SystemTime>,
+    ) -> Response {
+        let mut response = Response::new(Status::OK);
+
+        response.add_header("Accept-Ranges", "bytes");
+        if let Some(etag) = etag {
+            response.set_etag(etag);
+        }
+        if let Some(last_modified) = last_modified {
+            response.set_last_modified(last_modified);
+        }
+        response.set_body(body);
+
+        let if_range_passes = match request.get_header("If-Range") {
+            // Dates can start with `W` too (`Wed, ...`), only `"` and `W/` start an entity tag
+            Some(if_range)
+                if if_range.trim().starts_with('"') || if_range.trim().starts_with("W/") =>
+            {
+                match (ETag::try_from(if_range.as_str()), etag) {
+                    (Ok(if_range), Some(etag)) => if_range.strong_eq(etag),
+                    _ => false,
+                }
+            }
+            // Dates only validate the range if they match the last modification date exactly
+            Some(if_range) => match (parse_http_date(&if_range), last_modified) {
+                (Some(if_range), Some(last_modified)) => {
+                    format_http_date(if_range) == format_http_date(last_modified)
+                }
+                _ => false,
+            },
+            None => true,
+        };
+
+        let ranges = request.range().and_then(|range| range.byte_ranges());
+
+        if let (true, Some([range])) = (if_range_passes, ranges.as_deref()) {
+            response.set_range(range);
+        }
+
+        response
+    }
+
+    /// Generates an error response with the given status and a minimal HTML page showing the message, the message is escaped so it can't inject markup.
+    pub fn from_error(status: Status, message: &str) -> Self {
+        let title = escape_html(&status.to_string());
+        let message = escape_html(message);
+
+        let mut response = Response::new(status);
+
+        response.add_header("Content-Type", "text/html");
+        response.body(format!(
+            "<!DOCTYPE html>\n<html>\n<head><title>{}</title></head>\n<body>\n<h1>{}</h1>\n<p>{}</p>\n</body>\n</html>\n",
+            title, title, message
+        ));
+
+        response
+    }
+
+    /// Generates an RFC 7807 `application/problem+json` error response for APIs, with the `type` (Always `about:blank`), `title`, `status` and `detail` fields.
+    #[cfg(feature = "json")]
+    pub fn problem(status: Status, title: &str, detail: &str) -> Self {
+        let problem = serde_json::json!({
+            "type": "about:blank",
+            "title": title,
+            "status": status.code(),
+            "detail": detail,
+        });
+
+        let mut response = Response::new(status);
+
+        // Serializing a JSON value can't fail
+        let _ = response.set_json_body(&problem);
+        response.add_header("Content-Type", "application/problem+json");
+
+        response
+    }
+
+    /// Generates a response for a stream of server-sent events, with the `text/event-stream` content type and the caching and connection headers the events need. The events are written with a [SseWriter].
+    pub fn sse() -> Self {
+        let mut response = Response::new(Status::OK);
+
+        response.add_header("Content-Type", "text/event-stream");
+        response.add_header("Cache-Control", "no-cache");
+        response.add_header("Connection", "keep-alive");
+
+        response
+    }
+
+    /// Generates a `405 Method Not Allowed` response with the `Allow` header listing the given methods, check [crate::router::RouterError::MethodNotAllowed].
+    pub fn method_not_allowed(methods: &[Method]) -> Self {
+        let allow: Vec<String> = methods.iter().map(|method| method.to_string()).collect();
+
+        let mut response = Response::new(Status::MethodNotAllowed);
+        response.add_header("Allow", &allow.join(", "));
+
+        response
+    }
+
+    /// Generates a `103 Early Hints` interim response with a `Link` header for each link (Like `</style.css>; rel=preload; as=style`), sent before the final response so the client can start preloading the resources.
+    pub fn early_hints(links: &[&str]) -> Self {
+        let mut response = Response::new(Status::EarlyHints);
+
+        // Each link is sent on its own `Link` line
+        response.add_header("Link", &links.join("\n"));
+
+        response
+    }
+
+    /// Generates the response sent to the client by a reverse proxy from the response of the upstream server. The status, end-to-end headers and body are kept, the hop-by-hop headers are removed (Check [crate::package::is_hop_by_hop_header]) and the framing is recomputed, so a chunked upstream response is sent with a `Content-Length`.
+    pub fn pipe_from(upstream: Response) -> Response {
+        let mut response = Response::new(upstream.status.clone());
+
+        response.copy_end_to_end_headers_from(&upstream);
+        response.remove_header("Content-Length");
+
+        response.body = upstream.body;
+        response.file_body = upstream.file_body;
+        response.stream_body = upstream.stream_body;
+
+        let content_length = match (&response.body, &response.file_body) {
+            (_, Some(file_body)) => Some(file_body.len as usize),
+            (Some(body), None) => Some(body.len()),
+            (None, None) => None,
+        };
+
+        if let (Some(content_length), None) = (content_length, &response.stream_body) {
+            response.add_header("Content-Length", &content_length.to_string());
+        }
+
+        response
+    }
+
+    /// Generates a `412 Precondition Failed` response, used when a conditional request precondition fails.
+    pub fn precondition_failed() -> Self {
+        Response::new(Status::PreconditionFailed)
+    }
+
+    /// Sets the `ETag` header of the response.
+    pub fn set_etag(&mut self, etag: &ETag) {
+        self.add_header("ETag", &etag.to_string());
+    }
+
+    /// Redirects the user to the specified path with `301 Moved Permanently`. Returns a [ResponseError::InvalidRedirectStatus] if the response has an informational (1xx) status, since interim responses can't be turned into a redirect.
+    pub fn redirect(&mut self, location: &str) -> Result<(), crate::Error> {
+        if self.status.is_informational() {
+            return Err(crate::Error::ResponseError(
+                ResponseError::InvalidRedirectStatus(self.status.clone()),
+            ));
         }
+
+        self.redirect_with_status(location, Status::MovedPermanently)
     }
 
-    /// Redirects the user to the specified path.
-    pub fn redirect(&mut self, location: &str) {
+    /// Redirects the user to the specified path with the given redirect status. Returns an error if the status isn't a redirect status, check [Status::is_redirect].
+    pub fn redirect_with_status(
+        &mut self,
+        location: &str,
+        status: Status,
+    ) -> Result<(), crate::Error> {
+        if !status.is_redirect() {
+            return Err(crate::Error::ResponseError(
+                ResponseError::InvalidRedirectStatus(status),
+            ));
+        }
+
         self.add_header("Location", location);
-        self.status = Status::MovedPermanently;
+        self.status = status;
+
+        Ok(())
     }
 
     // Should be moved to the package trait
-    /// Sets the body of the response to a string.
+    /// Sets the body of the response to a string. Sets the `Content-Type` to `text/plain; charset=utf-8` if no content type was set.
     pub fn set_body_string(&mut self, body: String) {
+        self.body(body);
+    }
+
+    /// Sets an HTML body rendered from the template, replacing the `{{name}}` placeholders with the HTML escaped value of their variable. Placeholders without variable are kept as is, check [Response::set_body_template_with].
+    pub fn set_body_template(&mut self, template: &str, vars: &HashMap<&str, &str>) {
+        self.set_body_template_with(template, vars, UnmatchedPlaceholder::Keep);
+    }
+
+    /// Sets an HTML body rendered from the template like [Response::set_body_template], handling the placeholders without variable as given. The `Content-Type` is set to `text/html`.
+    pub fn set_body_template_with(
+        &mut self,
+        template: &str,
+        vars: &HashMap<&str, &str>,
+        unmatched: UnmatchedPlaceholder,
+    ) {
+        let body = template::render(template, vars, unmatched, escape_html);
+
+        self.add_header("Content-Type", "text/html");
         self.set_body(body.into_bytes());
     }
 
+    /// Appends the bytes to the body of the response, so the body can be built in several steps without copying it again. The `Content-Length` is computed when the response is sent. Only the in-memory body is extended, a file or stream body is replaced like [Package::set_body] does.
+    pub fn append_body(&mut self, bytes: &[u8]) {
+        self.file_body = None;
+        self.stream_body = None;
+
+        self.body
+            .get_or_insert_with(Vec::new)
+            .extend_from_slice(bytes);
+    }
+
+    /// Appends the string to the body of the response, check [Response::append_body].
+    pub fn append_body_str(&mut self, text: &str) {
+        self.append_body(text.as_bytes());
+    }
+
+    /// Sets the body of the response, setting the default content type of the body type if no content type was set. Check [IntoBody] for the supported types.
+    pub fn body(&mut self, body: impl IntoBody) -> &mut Self {
+        if self.get_header("Content-Type").is_none() {
+            self.add_header("Content-Type", body.default_content_type());
+        }
+
+        self.set_body(body.into_body());
+        self
+    }
+
+    /// Serializes the value as compact JSON and sets it as the body, with the `application/json` content type.
+    #[cfg(feature = "json")]
+    pub fn set_json_body<T: serde::Serialize>(&mut self, value: &T) -> Result<(), crate::Error> {
+        self.set_json(serde_json::to_vec(value))
+    }
+
+    /// Serializes the value as pretty printed JSON and sets it as the body, with the `application/json` content type.
+    #[cfg(feature = "json")]
+    pub fn set_json_body_pretty<T: serde::Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), crate::Error> {
+        self.set_json(serde_json::to_vec_pretty(value))
+    }
+
+    #[cfg(feature = "json")]
+    fn set_json(&mut self, json: serde_json::Result<Vec<u8>>) -> Result<(), crate::Error> {
+        let json = json.map_err(ResponseError::JsonError)?;
+
+        self.add_header("Content-Type", "application/json");
+        self.set_body(json);
+
+        Ok(())
+    }
+
+    /// Adds the header only if the response doesn't have a header with the same key (ignoring case), so a default set by a middleware doesn't replace the value chosen by the handler.
+    pub fn set_header_if_absent(&mut self, key: &str, value: &str) {
+        if self.get_header(key).is_none() {
+            self.add_header(key, value);
+        }
+    }
+
+    /// Sets a new cookie with the given attributes, like `["Secure", "Path=/", "Max-Age=3600"]`. Cookies with the `__Secure-` prefix must have the `Secure` attribute and cookies with the `__Host-` prefix must also have `Path=/` and no `Domain`, returns a [ResponseError::InvalidCookiePrefix] if they don't (Browsers would reject them).
+    pub fn set_cookie(
+        &mut self,
+        name: &str,
+        value: &str,
+        attributes: &[&str],
+    ) -> Result<(), crate::Error> {
+        let lowercase_name = name.to_ascii_lowercase();
+
+        let has_attribute = |expected: &str| {
+            attributes.iter().any(|attribute| {
+                attribute
+                    .split_once('=')
+                    .map_or(attribute.trim(), |(key, _)| key.trim())
+                    .eq_ignore_ascii_case(expected)
+            })
+        };
+        let has_root_path = attributes.iter().any(|attribute| {
+            attribute.split_once('=').is_some_and(|(key, path)| {
+                key.trim().eq_ignore_ascii_case("Path") && path.trim() == "/"
+            })
+        });
+
+        let is_valid = match lowercase_name.as_str() {
+            name if name.starts_with("__host-") => {
+                has_attribute("Secure") && has_root_path && !has_attribute("Domain")
+            }
+            name if name.starts_with("__secure-") => has_attribute("Secure"),
+            _ => true,
+        };
+
+        if !is_valid {
+            return Err(crate::Error::ResponseError(
+                ResponseError::InvalidCookiePrefix(String::from(name)),
+            ));
+        }
+
+        let mut cookie = format!("{}={}", name, value);
+
+        for attribute in attributes {
+            cookie.push_str("; ");
+            cookie.push_str(attribute.trim());
+        }
+
+        self.add_header("Set-Cookie", &cookie);
+
+        Ok(())
+    }
+
     /// Sets a new session cookie (with the HttpOnly flag).
     pub fn set_session_cookie(&mut self, name: &str, value: &str) {
         self.add_header("Set-Cookie", &format!("{}={}; HttpOnly", name, value));
     }
 
+    /// Sets a new session cookie (with the HttpOnly flag) signed with HMAC-SHA256 along with its name, so tampered values or values moved to another cookie are rejected by [crate::request::Request::signed_cookie].
+    #[cfg(feature = "signed-cookies")]
+    pub fn set_signed_cookie(&mut self, name: &str, value: &str, key: &[u8]) {
+        self.set_session_cookie(name, &crate::signing::sign(name, value, key));
+    }
+
+    /// Adds a request header to the `Vary` header, ignoring the headers that were already added. Adding `*` replaces every header, and once `Vary` is `*` any other header is ignored.
+    pub fn add_vary(&mut self, header: &str) {
+        let mut vary_headers = self.header_list("Vary");
+
+        let header = header.trim();
+
+        if vary_headers.iter().any(|vary_header| vary_header == "*") {
+            return;
+        }
+
+        if header == "*" {
+            vary_headers = vec![String::from("*")];
+        } else if !vary_headers
+            .iter()
+            .any(|vary_header| vary_header.eq_ignore_ascii_case(header))
+        {
+            vary_headers.push(String::from(header));
+        }
+
+        self.remove_header("Vary");
+        self.add_header("Vary", &vary_headers.join(", "));
+    }
+
+    /// Adds the request headers the negotiated aspects were chosen from to the `Vary` header (Check [Response::add_vary]), in the given order. Caches need them to store a different response for every variant.
+    pub fn vary_by_negotiation(&mut self, negotiated: &[Negotiation]) {
+        for negotiation in negotiated {
+            self.add_vary(negotiation.header());
+        }
+    }
+
+    /// Sets the WebSocket subprotocol selected by the server (`Sec-WebSocket-Protocol`) in the `101 Switching Protocols` response, it must be one of the subprotocols offered by the client.
+    pub fn set_websocket_protocol(&mut self, protocol: &str) {
+        self.add_header("Sec-WebSocket-Protocol", protocol);
+    }
+
+    /// Sets the `Last-Modified` header, formatted as an HTTP-date (Check [crate::date::format_http_date]).
+    pub fn set_last_modified(&mut self, time: SystemTime) {
+        self.add_header("Last-Modified", &format_http_date(time));
+    }
+
+    /// Sets the `Expires` header, formatted as an HTTP-date.
+    pub fn set_expires(&mut self, time: SystemTime) {
+        self.add_header("Expires", &format_http_date(time));
+    }
+
+    /// Returns the time of the `Expires` header, None if the response doesn't have one. Invalid dates (like `0`) mean the response has already expired and are returned as the unix epoch.
+    pub fn expires(&self) -> Option<SystemTime> {
+        self.get_header("Expires")
+            .map(|expires| parse_http_date(&expires).unwrap_or(UNIX_EPOCH))
+    }
+
+    /// Sets the `Content-Language` header with the given language tags (`en, fr`). Returns an error if there are no tags or a tag isn't a valid token (Only letters, digits and `-`).
+    pub fn set_content_language(&mut self, langs: &[&str]) -> Result<(), crate::Error> {
+        if langs.is_empty() {
+            return Err(crate::Error::ResponseError(
+                ResponseError::InvalidLanguageTag(String::new()),
+            ));
+        }
+
+        for lang in langs {
+            let is_valid = !lang.is_empty()
+                && lang
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-');
+
+            if !is_valid {
+                return Err(crate::Error::ResponseError(
+                    ResponseError::InvalidLanguageTag(String::from(*lang)),
+                ));
+            }
+        }
+
+        self.add_header("Content-Language", &langs.join(", "));
+
+        Ok(())
+    }
+
+    /// Sets the `Preference-Applied` header with the preferences of the `Prefer` request header that were honored (Check [crate::request::Request::prefer]). Preferences with an empty value are sent without value.
+    pub fn set_preference_applied(&mut self, preferences: &[(&str, &str)]) {
+        let applied: Vec<String> = preferences
+            .iter()
+            .map(|(name, value)| match value.is_empty() {
+                true => name.to_string(),
+                false => format!("{}={}", name, value),
+            })
+            .collect();
+
+        self.add_header("Preference-Applied", &applied.join(", "));
+    }
+
+    /// Keeps the connection open after the response (`Connection: keep-alive`), advertising how long an idle connection is kept in seconds and how many more requests it accepts with the `Keep-Alive` header. Used with HTTP/1.0 clients, which close the connection by default.
+    pub fn set_keep_alive_params(&mut self, timeout: u32, max: u32) {
+        self.add_header("Connection", "keep-alive");
+        self.add_header("Keep-Alive", &format!("timeout={}, max={}", timeout, max));
+    }
+
+    /// Sets the `Retry-After` header, used along [Status::TooManyRequests] or [Status::ServiceUnavailable].
+    pub fn set_retry_after(&mut self, after: RetryAfter) {
+        self.add_header("Retry-After", &after.to_header_value());
+    }
+
+    /// Sets the `Content-Disposition` header so the client downloads the body as a file with the given name. Non ASCII names are sent using the `filename*` parameter, with an ASCII fallback on `filename`.
+    pub fn set_attachment(&mut self, filename: &str) {
+        let ascii_filename: String = filename
+            .chars()
+            .map(|c| match c {
+                '"' | '\\' => format!("\\{}", c),
+                c if c.is_ascii() && !c.is_ascii_control() => c.to_string(),
+                _ => String::from("_"),
+            })
+            .collect();
+
+        let mut content_disposition = format!("attachment; filename=\"{}\"", ascii_filename);
+
+        if !filename.is_ascii() {
+            let encoded_filename: String = filename
+                .bytes()
+                .map(|byte| match byte {
+                    b if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) => {
+                        (b as char).to_string()
+                    }
+                    b => format!("%{:02X}", b),
+                })
+                .collect();
+
+            content_disposition.push_str(&format!("; filename*=UTF-8''{}", encoded_filename));
+        }
+
+        self.add_header("Content-Disposition", &content_disposition);
+    }
+
+    /// Sets an open file as the body of the response, the next `len` bytes of the file (From its current position) are streamed to the client when the response is sent instead of being read into memory. Returns an error if the current position of the file can't be read.
+    pub fn set_file_body(&mut self, mut file: File, len: u64) -> Result<(), crate::Error> {
+        let start = file.stream_position()?;
+
+        self.body = None;
+        self.stream_body = None;
+        self.file_body = Some(FileBody {
+            file: Arc::new(Mutex::new(file)),
+            start,
+            len,
+        });
+
+        self.add_header("Content-Length", &len.to_string());
+
+        Ok(())
+    }
+
+    /// Sets a reader of unknown length as the body of the response, it's streamed to the client until it reaches EOF. HTTP/1.1 responses are sent using `Transfer-Encoding: chunked`, HTTP/1.0 responses (which don't support chunked bodies) are sent with `Connection: close` and the body ends when the connection closes.
+    pub fn set_stream_body(&mut self, reader: impl Read + Send + 'static) {
+        self.body = None;
+        self.file_body = None;
+        self.stream_body = Some(StreamBody {
+            reader: Arc::new(Mutex::new(Box::new(reader))),
+        });
+    }
+
+    /// Returns the length in bytes of the body without serializing the response. File bodies return their length, stream bodies return the declared `Content-Length` (0 if they don't declare it, since their length is unknown until they are sent).
+    pub fn body_len(&self) -> usize {
+        match (&self.body, &self.file_body, &self.stream_body) {
+            (_, Some(file_body), _) => file_body.len as usize,
+            (_, _, Some(_)) => self
+                .get_header("Content-Length")
+                .and_then(|content_length| content_length.trim().parse().ok())
+                .unwrap_or(0),
+            (Some(body), _, _) => body.len(),
+            (None, None, None) => 0,
+        }
+    }
+
+    /// Returns true if the response has no body or an empty one, check [Response::body_len].
+    pub fn body_is_empty(&self) -> bool {
+        self.body_len() == 0
+    }
+
+    /// Reads the body of the response from the reader until EOF, reading at most `max` bytes. Returns a [ResponseError::BodyTooLarge] if the reader has more than `max` bytes, the body of the response is left untouched in that case. Used to cap the bodies received from upstream servers.
+    pub fn set_body_reader_limited(
+        &mut self,
+        reader: impl Read,
+        max: u64,
+    ) -> Result<(), crate::Error> {
+        let mut body = Vec::new();
+
+        // One extra byte is read to know if the reader exceeds the limit
+        reader.take(max.saturating_add(1)).read_to_end(&mut body)?;
+
+        if body.len() as u64 > max {
+            return Err(crate::Error::ResponseError(ResponseError::BodyTooLarge(
+                max,
+            )));
+        }
+
+        self.set_body(body);
+
+        Ok(())
+    }
+
+    /// Writes the response into the writer, streaming the file or stream body if it has one (check [Response::set_file_body] and [Response::set_stream_body]).
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(self.status_line().as_bytes())?;
+        writer.write_all(self.header_lines().as_bytes())?;
+        writer.write_all(b"\r\n")?;
+
+        if !self.status.allows_body() || self.head {
+            return Ok(());
+        }
+
+        if let Some(stream_body) = &self.stream_body {
+            let mut reader = stream_body
+                .reader
+                .lock()
+                .map_err(|_| io::Error::other("Stream body reader poisoned"))?;
+
+            match self.version {
+                HttpVersion::Http10 => {
+                    io::copy(&mut *reader, writer)?;
+                }
+                _ => write_chunked(&mut *reader, writer)?,
+            }
+        } else if let Some(file_body) = &self.file_body {
+            let mut file = file_body
+                .file
+                .lock()
+                .map_err(|_| io::Error::other("File body poisoned"))?;
+
+            file.seek(SeekFrom::Start(file_body.start))?;
+
+            // A file shorter than the advertised length would leave the client waiting for the rest of the body
+            if io::copy(&mut (&mut *file).take(file_body.len), writer)? != file_body.len {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+        } else if let Some(body) = &self.body {
+            writer.write_all(body)?;
+        }
+
+        Ok(())
+    }
+
+    /// Serializes the response into bytes, the body is kept untouched so binary bodies aren't corrupted like with [Response::to_string]. File and stream bodies are read into the result, returns an error if they can't be read instead of returning a truncated body.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, crate::Error> {
+        let mut resp = Vec::new();
+
+        self.write_to(&mut resp)?;
+
+        Ok(resp)
+    }
+
+    /// Returns the body decoded according to the `Content-Encoding` header, the codings are removed in the reverse order they were applied. Supports `gzip`, `deflate` and `br`, returns a [ResponseError::UnsupportedEncoding] for any other coding.
+    ///
+    /// Decoding stops with a [ResponseError::BodyTooLarge] as soon as any decoded body is longer than `max_size` bytes, so a small compressed body can't expand into a huge one.
+    #[cfg(feature = "compression")]
+    pub fn decoded_body(&self, max_size: usize) -> Result<Vec<u8>, crate::Error> {
+        let mut body = self.body.clone().unwrap_or_default();
+
+        for coding in self.header_list("Content-Encoding").into_iter().rev() {
+            let decoder: Box<dyn Read + '_> = match coding.to_lowercase().as_str() {
+                "identity" => continue,
+                "gzip" | "x-gzip" => Box::new(flate2::read::GzDecoder::new(body.as_slice())),
+                "deflate" => Box::new(flate2::read::ZlibDecoder::new(body.as_slice())),
+                "br" => Box::new(brotli::Decompressor::new(body.as_slice(), 4096)),
+                coding => {
+                    return Err(crate::Error::ResponseError(
+                        ResponseError::UnsupportedEncoding(String::from(coding)),
+                    ))
+                }
+            };
+
+            // One extra byte tells a body of exactly max_size bytes from a longer one
+            let mut decoded = Vec::new();
+            decoder
+                .take(max_size as u64 + 1)
+                .read_to_end(&mut decoded)?;
+
+            if decoded.len() > max_size {
+                return Err(crate::Error::ResponseError(ResponseError::BodyTooLarge(
+                    max_size as u64,
+                )));
+            }
+
+            body = decoded;
+        }
+
+        Ok(body)
+    }
+
     /// Sets the body of the response to the contents of a file.
     pub fn send_file<P>(&mut self, path: P) -> Result<(), crate::Error>
     where
@@ -74,9 +757,22 @@ impl Response {
 
 impl Response {
     pub(crate) fn pack(&mut self) {
-        let content_length = match self.body.as_ref() {
-            Some(body) => body.len().to_string(),
-            None => "0".to_string(),
+        if !self.status.allows_body() {
+            return;
+        }
+
+        if self.stream_body.is_some() {
+            if !self.has_header("Content-Type") {
+                self.add_header("Content-Type", "text/plain");
+            }
+
+            return;
+        }
+
+        let content_length = match (self.body.as_ref(), self.file_body.as_ref()) {
+            (_, Some(file_body)) => file_body.len.to_string(),
+            (Some(body), None) => body.len().to_string(),
+            (None, None) => "0".to_string(),
         };
 
         self.add_header("Content-Length", &content_length);
@@ -85,20 +781,96 @@ impl Response {
             self.add_header("Content-Type", "text/plain");
         }
     }
+
+    // Multiple values of the same header (Set-Cookie) are stored separated by new lines, each one is sent on its own line.
+    // The framing headers of stream bodies replace the ones set by the user.
+    fn header_lines(&self) -> String {
+        let mut lines = String::new();
+
+        let framing_headers: &[(&str, &str)] = match (&self.stream_body, &self.version) {
+            (None, _) => &[],
+            (Some(_), HttpVersion::Http10) => &[("Connection", "close")],
+            (Some(_), _) => &[("Transfer-Encoding", "chunked")],
+        };
+
+        for (key, value) in &self.headers {
+            let replaced_by_framing = self.stream_body.is_some()
+                && ["Content-Length", "Transfer-Encoding", "Connection"]
+                    .iter()
+                    .any(|header| header.eq_ignore_ascii_case(key));
+
+            if replaced_by_framing {
+                continue;
+            }
+
+            for line_value in value.split('\n') {
+                lines.push_str(&format!("{}: {}\r\n", key, line_value));
+            }
+        }
+
+        for (key, value) in framing_headers {
+            lines.push_str(&format!("{}: {}\r\n", key, value));
+        }
+
+        lines
+    }
+
+    fn serialized_body(&self) -> Option<&Vec<u8>> {
+        match self.status.allows_body() && !self.head {
+            true => self.body.as_ref(),
+            false => None,
+        }
+    }
+
+    fn status_line(&self) -> String {
+        match self.omit_reason_phrase {
+            true => format!("{} {}\r\n", self.version, self.status.code()),
+            false => format!("{} {}\r\n", self.version, self.status),
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '&' => String::from("&amp;"),
+            '<' => String::from("&lt;"),
+            '>' => String::from("&gt;"),
+            '"' => String::from("&quot;"),
+            '\'' => String::from("&#39;"),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn write_chunked<R: Read, W: Write>(reader: &mut R, writer: &mut W) -> io::Result<()> {
+    let mut buffer = [0; 8192];
+
+    loop {
+        let bytes_read = reader.read(&mut buffer)?;
+
+        if bytes_read == 0 {
+            break;
+        }
+
+        writer.write_all(format!("{:X}\r\n", bytes_read).as_bytes())?;
+        writer.write_all(&buffer[..bytes_read])?;
+        writer.write_all(b"\r\n")?;
+    }
+
+    writer.write_all(b"0\r\n\r\n")
 }
 
 /// Implementation of the Display trait for the Response struct. WILL REPLACE NON VALID ASCII CHARS WITH "�".
 impl Display for Response {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut resp = format!("HTTP/1.1 {}\r\n", self.status);
+        let mut resp = self.status_line();
 
-        for (key, value) in &self.headers {
-            resp.push_str(&format!("{}: {}\r\n", key, value));
-        }
+        resp.push_str(&self.header_lines());
 
         resp.push_str("\r\n");
 
-        if let Some(body) = &self.body {
+        if let Some(body) = self.serialized_body() {
             resp.push_str(String::from_utf8_lossy(body).as_ref());
         }
 
@@ -106,20 +878,133 @@ impl Display for Response {
     }
 }
 
-impl BinaryRepresentation for Response {
-    fn to_binary(&self) -> Vec<u8> {
-        let mut resp = format!("HTTP/1.1 {}\r\n", self.status).into_bytes();
+/// Parses a response received from a server, like a client would. Chunked bodies are decoded, the rest of the data is used as the body otherwise.
+impl TryFrom<Vec<u8>> for Response {
+    type Error = crate::Error;
 
-        for (key, value) in &self.headers {
-            resp.extend_from_slice(format!("{}: {}\r\n", key, value).as_bytes());
+    fn try_from(binary_data: Vec<u8>) -> Result<Self, Self::Error> {
+        let invalid_response = |data: &[u8]| {
+            crate::Error::ResponseError(ResponseError::InvalidResponse(
+                String::from_utf8_lossy(data).to_string(),
+            ))
+        };
+
+        let (header, body) = package::find_header_end(&binary_data)
+            .map(|(header_end, body_start)| {
+                (&binary_data[..header_end], &binary_data[body_start..])
+            })
+            .ok_or_else(|| invalid_response(&binary_data))?;
+
+        let header_string = String::from_utf8_lossy(header);
+        let mut lines = header_string.lines();
+
+        let status_line = lines.next().unwrap_or("");
+        let version = status_line.split(' ').next().unwrap_or("");
+
+        if !version.starts_with("HTTP/") {
+            return Err(invalid_response(header));
         }
 
-        resp.extend_from_slice("\r\n".as_bytes());
+        let status = status_line
+            .parse::<Status>()
+            .map_err(|_| invalid_response(header))?;
+
+        let mut response = Response::new(status);
+        response.version = HttpVersion::from(version);
+
+        for line in lines {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| invalid_response(header))?;
+            let (key, value) = (key.trim(), value.trim());
 
-        if let Some(body) = &self.body {
-            resp.extend_from_slice(body);
+            // Repeated headers (Like `Link` or `Warning`) are joined in a comma separated list, add_header already keeps every `Set-Cookie` and `Vary` value
+            let value = match response.get_header(key) {
+                Some(previous)
+                    if !key.eq_ignore_ascii_case("Set-Cookie")
+                        && !key.eq_ignore_ascii_case("Vary") =>
+                {
+                    format!("{}, {}", previous, value)
+                }
+                _ => String::from(value),
+            };
+
+            response.add_header(key, &value);
         }
 
-        resp
+        let is_chunked = response
+            .get_header("Transfer-Encoding")
+            .is_some_and(|encoding| encoding.to_lowercase().contains("chunked"));
+
+        let body = match (is_chunked, response.get_header("Content-Length")) {
+            (true, _) => {
+                let mut remaining = body;
+                chunked::read_chunked_body(&mut remaining, &ParseConfig::default())?
+            }
+            // The bytes after the declared length belong to the next response
+            (false, Some(content_length)) => {
+                let content_length = content_length
+                    .parse::<usize>()
+                    .map_err(|_| invalid_response(header))?;
+
+                match body.get(..content_length) {
+                    Some(body) => body.to_vec(),
+                    None => {
+                        return Err(crate::Error::Io(std::io::Error::from(
+                            std::io::ErrorKind::UnexpectedEof,
+                        )))
+                    }
+                }
+            }
+            (false, None) => body.to_vec(),
+        };
+
+        response.set_body(body);
+
+        Ok(response)
+    }
+}
+
+/// Returns no bytes if the file or stream body can't be read, since a truncated body wouldn't match its `Content-Length`. Use [Response::to_bytes] to get the error.
+impl BinaryRepresentation for Response {
+    fn to_binary(&self) -> Vec<u8> {
+        self.to_bytes().unwrap_or_default()
     }
 }
+
+/// Contains all the possible errors that can occur when building a response.
+#[derive(Debug, thiserror::Error)]
+pub enum ResponseError {
+    /// The status can't be used to redirect the client.
+    #[error("Invalid redirect status: {0}")]
+    InvalidRedirectStatus(Status),
+
+    /// The language tag is empty or has invalid characters.
+    #[error("Invalid language tag: {0:?}")]
+    InvalidLanguageTag(String),
+
+    /// The value couldn't be serialized as JSON.
+    #[cfg(feature = "json")]
+    #[error("JSON serialization error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    /// The data isn't a valid response (No status line, malformed header...).
+    #[error("Invalid response\nRaw data:\n{0}")]
+    InvalidResponse(String),
+
+    /// The body is encoded with a content coding that can't be decoded, check [Response::decoded_body].
+    #[error("Unsupported content encoding: {0}")]
+    UnsupportedEncoding(String),
+
+    /// The body read into the response or decoded from it is bigger than the allowed limit, check [Response::set_body_reader_limited] and [Response::decoded_body].
+    #[error("Response body too large, the limit is {0} bytes")]
+    BodyTooLarge(u64),
+
+    /// The attributes of a `__Secure-` or `__Host-` cookie don't meet the requirements of its prefix, check [Response::set_cookie].
+    #[error("Cookie attributes don't meet the requirements of its prefix: {0}")]
+    InvalidCookiePrefix(String),
+
+    /// The status line doesn't have a three digit status code, check [Status::from_str].
+    #[error("Invalid status line: {0:?}")]
+    InvalidStatusLine(String),
+}
@@ -0,0 +1,23 @@
+/// Aspect of the response chosen from the request headers, check [crate::response::Response::vary_by_negotiation].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Negotiation {
+    /// The media type, chosen from the `Accept` header (Check [crate::request::Request::accepts]).
+    ContentType,
+
+    /// The content coding, chosen from the `Accept-Encoding` header (Check [crate::request::Request::preferred_encoding]).
+    Encoding,
+
+    /// The language, chosen from the `Accept-Language` header.
+    Language,
+}
+
+impl Negotiation {
+    /// Returns the name of the request header the aspect is chosen from.
+    pub fn header(&self) -> &'static str {
+        match self {
+            Negotiation::ContentType => "Accept",
+            Negotiation::Encoding => "Accept-Encoding",
+            Negotiation::Language => "Accept-Language",
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use std::time::{Duration, SystemTime};
+
+use crate::date::format_http_date;
+
+/// Value of the `Retry-After` header, tells the client when it should retry the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryAfter {
+    /// The client should retry after the given delay, sent in seconds.
+    Delay(Duration),
+
+    /// The client should retry after the given date.
+    Date(SystemTime),
+}
+
+impl RetryAfter {
+    /// Returns the value of the header.
+    pub fn to_header_value(&self) -> String {
+        match self {
+            RetryAfter::Delay(delay) => delay.as_secs().to_string(),
+            RetryAfter::Date(date) => format_http_date(*date),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::io::{self, Write};
+
+/// Writer of server-sent events (`text/event-stream`), formats each event and writes it into the inner writer. Check [super::Response::sse] for the headers of the response.
+#[derive(Debug)]
+pub struct SseWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> SseWriter<W> {
+    /// Generates a new event writer over the given writer (Usually the connection or the writing end of a stream body).
+    pub fn new(writer: W) -> Self {
+        SseWriter { writer }
+    }
+
+    /// Sends an event with an optional event type and id, multi-line data is sent as multiple `data:` lines (Lines can end with `\n`, `\r\n` or `\r`). The writer is flushed after the event so the client receives it right away.
+    ///
+    /// Returns an [io::ErrorKind::InvalidInput] error without writing anything if the event type or the id have a line break, or the id has a NUL character, since they would change the event.
+    pub fn send_event(
+        &mut self,
+        event: Option<&str>,
+        data: &str,
+        id: Option<&str>,
+    ) -> io::Result<()> {
+        let has_line_break = |field: &str| field.contains(['\n', '\r']);
+
+        if event.is_some_and(has_line_break)
+            || id.is_some_and(|id| has_line_break(id) || id.contains('\0'))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "The event type and id of an event can't have line breaks",
+            ));
+        }
+
+        let mut message = String::new();
+
+        if let Some(event) = event {
+            message.push_str(&format!("event: {}\n", event));
+        }
+
+        // Unlike str::lines, a trailing line break keeps the empty line after it
+        for line in data.split("\r\n").flat_map(|line| line.split(['\n', '\r'])) {
+            message.push_str(&format!("data: {}\n", line));
+        }
+
+        if let Some(id) = id {
+            message.push_str(&format!("id: {}\n", id));
+        }
+
+        message.push('\n');
+
+        self.writer.write_all(message.as_bytes())?;
+        self.writer.flush()
+    }
+
+    /// Returns the inner writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
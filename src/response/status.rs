@@ -1,4 +1,6 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
+
+use super::ResponseError;
 
 /// Contains all the supported response status codes.
 #[allow(missing_docs)]
@@ -89,127 +91,179 @@ impl TryFrom<u16> for Status {
     type Error = &'static str;
 
     fn try_from(status_code: u16) -> Result<Self, Self::Error> {
-        match status_code {
-            // 2xx
-            200 => Ok(Status::OK),
-            201 => Ok(Status::Created),
-            202 => Ok(Status::Accepted),
-
-            // 3xx
-            301 => Ok(Status::MovedPermanently),
-            302 => Ok(Status::Found),
-            303 => Ok(Status::SeeOther),
-            304 => Ok(Status::NotModified),
-
-            // 4xx
-            400 => Ok(Status::BadRequest),
-            401 => Ok(Status::Unauthorized),
-            403 => Ok(Status::Forbidden),
-            404 => Ok(Status::NotFound),
-            405 => Ok(Status::MethodNotAllowed),
-
-            // 5xx
-            500 => Ok(Status::InternalServerError),
-            501 => Ok(Status::NotImplemented),
-            502 => Ok(Status::BadGateway),
-            503 => Ok(Status::ServiceUnavailable),
-            505 => Ok(Status::HttpVersionNotSupported),
-
-            // Handle other status codes
-            val => Ok(Status::Other(val, "Unknown".to_string())),
+        Ok(Status::from_code(status_code))
+    }
+}
+
+/// Parses a status from a status line (`HTTP/1.1 404 Not Found`) or from the code and reason phrase (`404 Not Found`), the reason phrase is everything after the code. Known codes are mapped to their status, unknown codes are returned as [Status::Other] with the received reason phrase.
+impl FromStr for Status {
+    type Err = crate::Error;
+
+    fn from_str(status_line: &str) -> Result<Self, Self::Err> {
+        let invalid_status_line = || {
+            crate::Error::ResponseError(ResponseError::InvalidStatusLine(String::from(status_line)))
+        };
+
+        let mut status = status_line.trim();
+
+        if status.starts_with("HTTP/") {
+            status = status.split_once(' ').map_or("", |(_, status)| status);
+        }
+
+        let (code, reason) = status.split_once(' ').unwrap_or((status, ""));
+
+        if code.len() != 3 || !code.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(invalid_status_line());
+        }
+
+        let code = code.parse().map_err(|_| invalid_status_line())?;
+
+        match (Status::from_code(code), reason.trim()) {
+            (Status::Other(code, _), reason) if !reason.is_empty() => {
+                Ok(Status::Other(code, String::from(reason)))
+            }
+            (status, _) => Ok(status),
         }
     }
 }
 
-impl Display for Status {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str = match self {
-            // 1xx
-            Status::Continue => "100 Continue".to_string(),
-            Status::SwitchingProtocol => "101 Switching Protocols".to_string(),
-            Status::Processing => "102 Processing".to_string(),
-            Status::EarlyHints => "103 EarlyHints".to_string(),
-            Status::Checkpoint => "103 Checkpoint".to_string(),
-
-            // 2xx
-            Status::OK => "200 OK".to_string(),
-            Status::Created => "201 Created".to_string(),
-            Status::Accepted => "202 Accepted".to_string(),
-            Status::NonAuthoritativeInformation => "203 Non-Authoritative Information".to_string(),
-            Status::NoContent => "204 No Content".to_string(),
-            Status::ResetContent => "205 Reset Content".to_string(),
-            Status::PartialContent => "206 Partial Content".to_string(),
-            Status::MultiStatus => "207 Multi-Status".to_string(),
-            Status::AlreadyReported => "208 Already Reported".to_string(),
-            Status::IMUsed => "226 IM Used".to_string(),
-
-            // 3xx
-            Status::MultipleChoice => "300 Multiple Choices".to_string(),
-            Status::MovedPermanently => "301 Moved Permanently".to_string(),
-            Status::Found => "302 Found".to_string(),
-            Status::SeeOther => "303 See Other".to_string(),
-            Status::NotModified => "304 Not Modified".to_string(),
-            Status::UseProxy => "305 Use Proxy".to_string(),
-            Status::Unused => "306 Unused".to_string(),
-            Status::TemporaryRedirect => "307 Temporary Redirect".to_string(),
-            Status::PermanentRedirect => "308 Permanent Redirect".to_string(),
-
-            // 4xx
-            Status::BadRequest => "400 Bad Request".to_string(),
-            Status::Unauthorized => "401 Unauthorized".to_string(),
-            Status::PaymentRequired => "402 Payment Required".to_string(),
-            Status::Forbidden => "403 Forbidden".to_string(),
-            Status::NotFound => "404 Not Found".to_string(),
-            Status::MethodNotAllowed => "405 Method Not Allowed".to_string(),
-            Status::NotAcceptable => "406 Not Acceptable".to_string(),
-            Status::ProxyAuthenticationRequired => "407 Proxy Authentication Required".to_string(),
-            Status::RequestTimeout => "408 Request Timeout".to_string(),
-            Status::Conflict => "409 Conflict".to_string(),
-            Status::Gone => "410 Gone".to_string(),
-            Status::LengthRequired => "411 Length Required".to_string(),
-            Status::PreconditionFailed => "412 Precondition Failed".to_string(),
-            Status::PayloadTooLarge => "413 Payload/Content Too Large".to_string(),
-            Status::URITooLong => "414 URI Too Long".to_string(),
-            Status::UnsupportedMediaType => "415 Unsupported Media Type".to_string(),
-            Status::RequestedRangeNotSatisfiable => {
-                "416 Requested Range Not Satisfiable".to_string()
+macro_rules! generate_status_functions {
+    ($($status:ident => $code:expr, $reason:expr),*) => {
+        impl Status {
+            /// Returns the numeric code of the status.
+            pub fn code(&self) -> u16 {
+                match self {
+                    $(Status::$status => $code,)*
+                    Status::Other(code, _) => *code,
+                }
             }
-            Status::ExpectationFailed => "417 Expectation Failed".to_string(),
-            Status::ImATeapot => "418 I'm A Teapot".to_string(),
-            Status::MisdirectedRequest => "421 Misdirected Request".to_string(),
-            Status::UnprocessableEntity => "422 Unprocessable Entity".to_string(),
-            Status::Locked => "423 Locked".to_string(),
-            Status::FailedDependency => "424 Failed Dependency".to_string(),
-            Status::TooEarly => "425 Too Early".to_string(),
-            Status::UpgradeRequired => "426 Upgrade Required".to_string(),
-            Status::PreconditionRequired => "428 Precondition Required".to_string(),
-            Status::TooManyRequests => "429 Too Many Requests".to_string(),
-            Status::RequestHeaderFieldsTooLarge => {
-                "431 Request Header Fields Too Large".to_string()
+
+            /// Returns the status with the given code, unknown codes are returned as [Status::Other] with the `Unknown` reason phrase.
+            // Codes shared by several statuses (103) return the first one
+            #[allow(unreachable_patterns)]
+            pub fn from_code(code: u16) -> Self {
+                match code {
+                    $($code => Status::$status,)*
+                    code => Status::Other(code, String::from("Unknown")),
+                }
             }
-            Status::UnavailableForLegalReasons => "451 Unavailable For Legal Reasons".to_string(),
-
-            // 5xx
-            Status::InternalServerError => "500 Internal Server Error".to_string(),
-            Status::NotImplemented => "501 Not Implemented".to_string(),
-            Status::BadGateway => "502 Bad Gateway".to_string(),
-            Status::ServiceUnavailable => "503 Service Unavailable".to_string(),
-            Status::GatewayTimeout => "504 Gateway Timeout".to_string(),
-            Status::HttpVersionNotSupported => "505 HTTP Version Not Supported".to_string(),
-            Status::VariantAlsoNegotiates => "506 Variant Also Negotiates".to_string(),
-            Status::InsufficientStorage => "507 Insufficient Storage".to_string(),
-            Status::LoopDetected => "508 Loop Detected".to_string(),
-            Status::BandwidthLimitExceeded => "509 Bandwidth Limit Exceeded".to_string(),
-            Status::NotExtended => "510 Not Extended".to_string(),
-            Status::NetworkAuthenticationRequired => {
-                "511 Network Authentication Required".to_string()
+
+            /// Returns the reason phrase of the status.
+            pub fn reason(&self) -> &str {
+                match self {
+                    $(Status::$status => $reason,)*
+                    Status::Other(_, reason) => reason.as_str(),
+                }
             }
-            Status::NotUpdated => "512 Not Updated".to_string(),
-            Status::VersionMismatch => "513 Version Mismatch".to_string(),
+        }
+    };
+}
 
-            // Handle other status codes
-            Status::Other(code, message) => format!("{} {}", code, message),
-        };
-        write!(f, "{}", str)
+generate_status_functions!(
+    // 1xx
+    Continue => 100, "Continue",
+    SwitchingProtocol => 101, "Switching Protocols",
+    Processing => 102, "Processing",
+    EarlyHints => 103, "Early Hints",
+    Checkpoint => 103, "Checkpoint",
+
+    // 2xx
+    OK => 200, "OK",
+    Created => 201, "Created",
+    Accepted => 202, "Accepted",
+    NonAuthoritativeInformation => 203, "Non-Authoritative Information",
+    NoContent => 204, "No Content",
+    ResetContent => 205, "Reset Content",
+    PartialContent => 206, "Partial Content",
+    MultiStatus => 207, "Multi-Status",
+    AlreadyReported => 208, "Already Reported",
+    IMUsed => 226, "IM Used",
+
+    // 3xx
+    MultipleChoice => 300, "Multiple Choices",
+    MovedPermanently => 301, "Moved Permanently",
+    Found => 302, "Found",
+    SeeOther => 303, "See Other",
+    NotModified => 304, "Not Modified",
+    UseProxy => 305, "Use Proxy",
+    Unused => 306, "Unused",
+    TemporaryRedirect => 307, "Temporary Redirect",
+    PermanentRedirect => 308, "Permanent Redirect",
+
+    // 4xx
+    BadRequest => 400, "Bad Request",
+    Unauthorized => 401, "Unauthorized",
+    PaymentRequired => 402, "Payment Required",
+    Forbidden => 403, "Forbidden",
+    NotFound => 404, "Not Found",
+    MethodNotAllowed => 405, "Method Not Allowed",
+    NotAcceptable => 406, "Not Acceptable",
+    ProxyAuthenticationRequired => 407, "Proxy Authentication Required",
+    RequestTimeout => 408, "Request Timeout",
+    Conflict => 409, "Conflict",
+    Gone => 410, "Gone",
+    LengthRequired => 411, "Length Required",
+    PreconditionFailed => 412, "Precondition Failed",
+    PayloadTooLarge => 413, "Payload/Content Too Large",
+    URITooLong => 414, "URI Too Long",
+    UnsupportedMediaType => 415, "Unsupported Media Type",
+    RequestedRangeNotSatisfiable => 416, "Requested Range Not Satisfiable",
+    ExpectationFailed => 417, "Expectation Failed",
+    ImATeapot => 418, "I'm A Teapot",
+    MisdirectedRequest => 421, "Misdirected Request",
+    UnprocessableEntity => 422, "Unprocessable Entity",
+    Locked => 423, "Locked",
+    FailedDependency => 424, "Failed Dependency",
+    TooEarly => 425, "Too Early",
+    UpgradeRequired => 426, "Upgrade Required",
+    PreconditionRequired => 428, "Precondition Required",
+    TooManyRequests => 429, "Too Many Requests",
+    RequestHeaderFieldsTooLarge => 431, "Request Header Fields Too Large",
+    UnavailableForLegalReasons => 451, "Unavailable For Legal Reasons",
+
+    // 5xx
+    InternalServerError => 500, "Internal Server Error",
+    NotImplemented => 501, "Not Implemented",
+    BadGateway => 502, "Bad Gateway",
+    ServiceUnavailable => 503, "Service Unavailable",
+    GatewayTimeout => 504, "Gateway Timeout",
+    HttpVersionNotSupported => 505, "HTTP Version Not Supported",
+    VariantAlsoNegotiates => 506, "Variant Also Negotiates",
+    InsufficientStorage => 507, "Insufficient Storage",
+    LoopDetected => 508, "Loop Detected",
+    BandwidthLimitExceeded => 509, "Bandwidth Limit Exceeded",
+    NotExtended => 510, "Not Extended",
+    NetworkAuthenticationRequired => 511, "Network Authentication Required",
+    NotUpdated => 512, "Not Updated",
+    VersionMismatch => 513, "Version Mismatch"
+);
+
+impl Status {
+    /// Returns true if the status is an interim informational status (1xx).
+    pub fn is_informational(&self) -> bool {
+        (100..200).contains(&self.code())
+    }
+
+    /// Returns true if a response with this status can carry a body. Informational (1xx), 204 and 304 responses can't.
+    pub fn allows_body(&self) -> bool {
+        !self.is_informational() && !matches!(self, Status::NoContent | Status::NotModified)
+    }
+
+    /// Returns true if the status can be used to redirect the client (301, 302, 303, 307 or 308).
+    pub fn is_redirect(&self) -> bool {
+        matches!(
+            self,
+            Status::MovedPermanently
+                | Status::Found
+                | Status::SeeOther
+                | Status::TemporaryRedirect
+                | Status::PermanentRedirect
+        )
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.code(), self.reason())
     }
 }
@@ -0,0 +1,26 @@
+use std::{
+    fmt::Debug,
+    io::Read,
+    sync::{Arc, Mutex},
+};
+
+/// Reader used as the body of a response when its length isn't known, it's streamed to the client until it reaches EOF.
+#[derive(Clone)]
+pub(crate) struct StreamBody {
+    pub(crate) reader: Arc<Mutex<Box<dyn Read + Send>>>,
+}
+
+impl Debug for StreamBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamBody").finish_non_exhaustive()
+    }
+}
+
+// Two stream bodies are equal only if they share the same reader
+impl PartialEq for StreamBody {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.reader, &other.reader)
+    }
+}
+
+impl Eq for StreamBody {}
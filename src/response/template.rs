@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+/// What to do with the placeholders of a template that have no variable, check [crate::response::Response::set_body_template_with].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnmatchedPlaceholder {
+    /// The placeholder is kept as is, like `{{name}}`. Used by default, so missing variables are easy to spot.
+    #[default]
+    Keep,
+
+    /// The placeholder is removed.
+    Blank,
+}
+
+// Placeholders are `{{name}}`, whitespace around the name is ignored. The values are escaped with the given function
+pub(crate) fn render(
+    template: &str,
+    vars: &HashMap<&str, &str>,
+    unmatched: UnmatchedPlaceholder,
+    escape: fn(&str) -> String,
+) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let end = match rest[start + 2..].find("}}") {
+            Some(end) => start + 2 + end,
+            None => break,
+        };
+
+        rendered.push_str(&rest[..start]);
+
+        match (vars.get(rest[start + 2..end].trim()), unmatched) {
+            (Some(value), _) => rendered.push_str(&escape(value)),
+            (None, UnmatchedPlaceholder::Keep) => rendered.push_str(&rest[start..end + 2]),
+            (None, UnmatchedPlaceholder::Blank) => (),
+        }
+
+        rest = &rest[end + 2..];
+    }
+
+    rendered.push_str(rest);
+
+    rendered
+}
@@ -0,0 +1,9 @@
+use crate::{request::Request, response::Response};
+
+/// Code that runs around the handlers of a [super::RouteTable], like logging or authentication. Check [super::RouteTable::add_middleware].
+///
+/// A middleware receives the request and the rest of the chain as `next`, it can modify the request before calling `next`, modify the response returned by `next` or return its own response without calling `next` to short-circuit the chain.
+pub trait Middleware: Send + Sync {
+    /// Handles the request, calling `next` to run the rest of the chain and the handler.
+    fn handle(&self, request: Request, next: &dyn Fn(Request) -> Response) -> Response;
+}
@@ -1,5 +1,7 @@
+mod middleware;
 #[allow(missing_docs)]
 pub mod route;
+mod route_table;
 
 use std::{
     collections::HashMap,
@@ -7,7 +9,9 @@ use std::{
     path::{Path, PathBuf},
 };
 
+pub use middleware::Middleware;
 pub use route::Route;
+pub use route_table::{RouteMatch, RouteTable};
 
 use crate::{
     request::Request,
@@ -15,6 +19,9 @@ use crate::{
     Error,
 };
 
+/// Function that handles a request, receives the request and the default response and returns the response sent to the client.
+pub type Handler = fn(Request, Response) -> Response;
+
 /// Handles the routing of requests made by the client.
 #[derive(Debug, Clone)]
 pub struct Router {
@@ -128,10 +135,36 @@ impl Router {
     }
 }
 
+/// Normalizes a path collapsing repeated slashes and removing the trailing slash, `/a//b/` is normalized to `/a/b`. The root path and empty paths are normalized to `/`.
+pub fn normalize_path(path: &str) -> String {
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    format!("/{}", segments.join("/"))
+}
+
 /// Errors that can occur when routing requests.
 #[derive(Debug, thiserror::Error)]
 pub enum RouterError {
     /// Route not found.
     #[error("Route not found: {0:?}")]
     RouteNotFound(Route),
+
+    /// The route pattern is malformed (Empty parameter name, wildcard not at the end...).
+    #[error("Invalid route pattern: {0}")]
+    InvalidPattern(String),
+
+    /// The path matches routes, but none of them accepts the method of the request. Contains the allowed methods, check [crate::response::Response::method_not_allowed].
+    #[error("Method not allowed, allowed methods: {0:?}")]
+    MethodNotAllowed(Vec<crate::request::Method>),
+
+    /// The path of the route is empty, has control characters or is `*` for a method other than `OPTIONS`, check [Route::try_new].
+    #[error("Invalid route path: {0:?}")]
+    InvalidRoute(String),
+
+    /// The route matches the same paths as a route already registered, check [RouteTable::merge].
+    #[error("Route conflicts with a registered route: {0:?}")]
+    RouteConflict(Route),
 }
@@ -1,4 +1,9 @@
-use crate::request::{Method, Request};
+use crate::{
+    request::{Method, Request},
+    Error,
+};
+
+use super::RouterError;
 
 /// Represents a route of a request made by a client.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -18,6 +23,23 @@ impl Route {
             path: String::from(path),
         }
     }
+
+    /// Generates a new route validating the path, a leading slash is added if it's missing (`users` becomes `/users`). Returns an error if the path is empty or has control characters. `*` is accepted as is for `OPTIONS *` requests, and rejected for any other method like the request parser does.
+    pub fn try_new(method: Method, path: &str) -> Result<Self, Error> {
+        if path.is_empty()
+            || path.chars().any(char::is_control)
+            || (path == "*" && method != Method::OPTIONS)
+        {
+            return Err(Error::RouterError(RouterError::InvalidRoute(String::from(
+                path,
+            ))));
+        }
+
+        match path.starts_with('/') || path == "*" {
+            true => Ok(Route::new(method, path)),
+            false => Ok(Route::new(method, &format!("/{}", path))),
+        }
+    }
 }
 
 impl From<Request> for Route {
@@ -0,0 +1,406 @@
+use std::{borrow::Cow, collections::HashMap, fmt::Debug, sync::Arc};
+
+use crate::{
+    request::{Method, Request},
+    response::{Response, Status},
+    Error,
+};
+
+use super::{normalize_path, Handler, Middleware, Route, RouterError};
+
+/// Declarative table of routes, allows registering route patterns from data instead of code.
+///
+/// Patterns are made of `/` separated segments, where a segment can be:
+/// - A literal, like `users`
+/// - A named parameter, like `:id`, optionally with a constraint on its value: `:id<int>` (Only digits), `:id<uuid>` (An hyphenated UUID) or `:name<slug>` (Lowercase letters, digits and `-`). Paths whose values don't meet the constraint don't match the pattern
+/// - A named wildcard, like `*path`, which must be the last segment. The captured remainder is normalized with [normalize_path] and has no leading or trailing slashes, `/static//a/b/` captures `a/b` for `/static/*path`.
+///
+/// Patterns are compiled when they are registered, so resolving a route doesn't need to parse them again.
+///
+/// Requests dispatched with [RouteTable::dispatch] go through the registered middlewares (Check [Middleware]) before reaching the handler.
+#[derive(Clone, Default)]
+pub struct RouteTable {
+    routes: Vec<CompiledRoute>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRoute {
+    route: Route,
+    segments: Vec<Segment>,
+    handler: Option<Handler>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Param(String, Option<Constraint>),
+    Wildcard(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Constraint {
+    Int,
+    Uuid,
+    Slug,
+}
+
+impl Constraint {
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            Constraint::Int => value.bytes().all(|byte| byte.is_ascii_digit()),
+            Constraint::Uuid => {
+                let groups: Vec<&str> = value.split('-').collect();
+
+                groups.iter().map(|group| group.len()).eq([8, 4, 4, 4, 12])
+                    && groups
+                        .iter()
+                        .all(|group| group.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            }
+            Constraint::Slug => value
+                .bytes()
+                .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-'),
+        }
+    }
+}
+
+/// Result of resolving a request route against a [RouteTable].
+#[derive(Debug, Clone)]
+pub struct RouteMatch {
+    /// The registered route pattern that matched.
+    pub route: Route,
+
+    /// The values captured by the parameters and wildcards of the pattern.
+    pub params: HashMap<String, String>,
+
+    /// The handler registered for the route, if any.
+    pub handler: Option<Handler>,
+
+    /// True if a `HEAD` request was resolved using the `GET` route, the body of the response should be stripped.
+    pub head_fallback: bool,
+}
+
+impl RouteTable {
+    /// Generates a new empty route table.
+    pub fn new() -> Self {
+        RouteTable {
+            routes: Vec::new(),
+            middlewares: Vec::new(),
+        }
+    }
+
+    /// Generates a new route table from a list of method and pattern pairs. Returns an error if any pattern is malformed.
+    pub fn from_pairs(pairs: &[(Method, &str)]) -> Result<Self, Error> {
+        let mut table = RouteTable::new();
+
+        for (method, pattern) in pairs {
+            table.insert(Route::new(method.clone(), pattern))?;
+        }
+
+        Ok(table)
+    }
+
+    /// Registers a new route pattern without a handler. Returns an error if the pattern is malformed.
+    pub fn insert(&mut self, route: Route) -> Result<(), Error> {
+        self.register(route, None)
+    }
+
+    /// Registers a new route pattern with its handler. Returns an error if the pattern is malformed.
+    pub fn handle_route(&mut self, route: Route, handler: Handler) -> Result<(), Error> {
+        self.register(route, Some(handler))
+    }
+
+    /// Returns the registered route patterns in insertion order.
+    pub fn export(&self) -> Vec<Route> {
+        self.routes
+            .iter()
+            .map(|compiled| compiled.route.clone())
+            .collect()
+    }
+
+    /// Resolves a request route into the first registered pattern that matches it. `HEAD` requests without a `HEAD` route fall back to the `GET` route, setting [RouteMatch::head_fallback].
+    ///
+    /// Returns [RouterError::MethodNotAllowed] with the allowed methods if the path matches only routes of other methods, and [RouterError::RouteNotFound] if it doesn't match any route.
+    pub fn resolve(&self, request_route: &Route) -> Result<RouteMatch, Error> {
+        if let Some(route_match) = self.match_route(&request_route.method, &request_route.path) {
+            return Ok(route_match);
+        }
+
+        if request_route.method == Method::HEAD {
+            if let Some(mut route_match) = self.match_route(&Method::GET, &request_route.path) {
+                route_match.head_fallback = true;
+                return Ok(route_match);
+            }
+        }
+
+        let allowed_methods = self.allowed_methods(&request_route.path);
+
+        match allowed_methods.is_empty() {
+            true => Err(Error::RouterError(RouterError::RouteNotFound(
+                request_route.clone(),
+            ))),
+            false => Err(Error::RouterError(RouterError::MethodNotAllowed(
+                allowed_methods,
+            ))),
+        }
+    }
+
+    // Methods of the routes that match the path in registration order, `HEAD` is allowed by the `GET` routes
+    fn allowed_methods(&self, path: &str) -> Vec<Method> {
+        let mut methods: Vec<Method> = Vec::new();
+
+        for compiled in &self.routes {
+            if methods.contains(&compiled.route.method)
+                || match_segments(&compiled.segments, path).is_none()
+            {
+                continue;
+            }
+
+            methods.push(compiled.route.method.clone());
+        }
+
+        if methods.contains(&Method::GET) && !methods.contains(&Method::HEAD) {
+            methods.push(Method::HEAD);
+        }
+
+        methods
+    }
+
+    /// Registers the routes of the other table under the prefix, `/users/:id` is registered as `/api/users/:id` when merged under `/api` (And `/` as `/api`). The handlers are kept, the middlewares of the other table aren't merged.
+    ///
+    /// Returns [RouterError::RouteConflict] without registering any route if a merged route would match the same paths as a route of the same method already registered (Like `/api/:id` and `/api/:name`), and [RouterError::InvalidPattern] if the prefix is malformed.
+    pub fn merge(&mut self, prefix: &str, other: RouteTable) -> Result<(), Error> {
+        let prefix = prefix.trim_end_matches('/');
+
+        if !prefix.is_empty() {
+            compile_pattern(prefix)?;
+        }
+
+        let mut merged = Vec::with_capacity(other.routes.len());
+
+        for compiled in other.routes {
+            let path = match compiled.route.path.as_str() {
+                "/" if !prefix.is_empty() => String::from(prefix),
+                path => format!("{}{}", prefix, path),
+            };
+
+            let route = Route::new(compiled.route.method.clone(), &path);
+            let segments = compile_pattern(&path)?;
+
+            let conflicts = self.routes.iter().any(|registered| {
+                registered.route.method == route.method
+                    && same_paths(&registered.segments, &segments)
+            });
+
+            if conflicts {
+                return Err(Error::RouterError(RouterError::RouteConflict(route)));
+            }
+
+            merged.push(CompiledRoute {
+                route,
+                segments,
+                handler: compiled.handler,
+            });
+        }
+
+        self.routes.extend(merged);
+
+        Ok(())
+    }
+
+    /// Registers a middleware that runs around every dispatched request. Middlewares run in registration order, the first registered middleware is the outermost one.
+    pub fn add_middleware(&mut self, middleware: impl Middleware + 'static) {
+        self.middlewares.push(Arc::new(middleware));
+    }
+
+    /// Resolves the request route and runs its handler through the middleware chain. Returns [RouterError::MethodNotAllowed] with the allowed methods if the path only matches routes of other methods (Check [RouteTable::resolve]) and [RouterError::RouteNotFound] if no route with a handler matches, in both cases the middlewares aren't run. The body of `HEAD` responses resolved using the `GET` route is stripped, check [Response::for_head].
+    pub fn dispatch(&self, request: Request) -> Result<Response, Error> {
+        let route_match = self.resolve(&request.path)?;
+
+        let handler = match route_match.handler {
+            Some(handler) => handler,
+            None => {
+                return Err(Error::RouterError(RouterError::RouteNotFound(
+                    request.path.clone(),
+                )))
+            }
+        };
+
+        let endpoint = |request: Request| handler(request, Response::new(Status::OK));
+        let response = run_middlewares(&self.middlewares, request, &endpoint);
+
+        match route_match.head_fallback {
+            true => Ok(response.for_head()),
+            false => Ok(response),
+        }
+    }
+
+    fn register(&mut self, route: Route, handler: Option<Handler>) -> Result<(), Error> {
+        let segments = compile_pattern(&route.path)?;
+
+        self.routes.retain(|compiled| compiled.route != route);
+        self.routes.push(CompiledRoute {
+            route,
+            segments,
+            handler,
+        });
+
+        Ok(())
+    }
+
+    fn match_route(&self, method: &Method, path: &str) -> Option<RouteMatch> {
+        self.routes
+            .iter()
+            .filter(|compiled| &compiled.route.method == method)
+            .find_map(|compiled| {
+                match_segments(&compiled.segments, path).map(|captures| RouteMatch {
+                    route: compiled.route.clone(),
+                    params: captures
+                        .into_iter()
+                        .map(|(name, value)| (String::from(name), value.into_owned()))
+                        .collect(),
+                    handler: compiled.handler,
+                    head_fallback: false,
+                })
+            })
+    }
+}
+
+impl Debug for RouteTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouteTable")
+            .field("routes", &self.routes)
+            .field("middlewares", &self.middlewares.len())
+            .finish()
+    }
+}
+
+fn run_middlewares(
+    middlewares: &[Arc<dyn Middleware>],
+    request: Request,
+    endpoint: &dyn Fn(Request) -> Response,
+) -> Response {
+    match middlewares.split_first() {
+        Some((middleware, rest)) => {
+            middleware.handle(request, &|request| run_middlewares(rest, request, endpoint))
+        }
+        None => endpoint(request),
+    }
+}
+
+// Only borrows from the segments and the path (Except for wildcards that need to be normalized), the captures are converted into owned values once the route matches
+fn match_segments<'a>(
+    segments: &'a [Segment],
+    path: &'a str,
+) -> Option<Vec<(&'a str, Cow<'a, str>)>> {
+    let mut captures = Vec::new();
+    let mut rest = Some(path.strip_prefix('/')?);
+
+    for segment in segments {
+        if let Segment::Wildcard(name) = segment {
+            captures.push((name.as_str(), normalize_wildcard(rest.unwrap_or(""))));
+            return Some(captures);
+        }
+
+        let current = rest?;
+
+        let (path_segment, remainder) = match current.split_once('/') {
+            Some((path_segment, remainder)) => (path_segment, Some(remainder)),
+            None => (current, None),
+        };
+
+        match segment {
+            Segment::Literal(literal) if literal == path_segment => (),
+            Segment::Param(name, constraint)
+                if !path_segment.is_empty()
+                    && constraint.is_none_or(|constraint| constraint.matches(path_segment)) =>
+            {
+                captures.push((name.as_str(), Cow::Borrowed(path_segment)))
+            }
+            _ => return None,
+        }
+
+        rest = remainder;
+    }
+
+    match rest {
+        Some(_) => None,
+        None => Some(captures),
+    }
+}
+
+// True if both patterns match the same paths, the names of the parameters and wildcards don't matter
+fn same_paths(segments: &[Segment], other_segments: &[Segment]) -> bool {
+    segments.len() == other_segments.len()
+        && segments
+            .iter()
+            .zip(other_segments)
+            .all(|segment_pair| match segment_pair {
+                (Segment::Literal(literal), Segment::Literal(other_literal)) => {
+                    literal == other_literal
+                }
+                (Segment::Param(_, constraint), Segment::Param(_, other_constraint)) => {
+                    constraint == other_constraint
+                }
+                (Segment::Wildcard(_), Segment::Wildcard(_)) => true,
+                _ => false,
+            })
+}
+
+fn normalize_wildcard(remainder: &str) -> Cow<'_, str> {
+    let is_normalized =
+        !remainder.starts_with('/') && !remainder.ends_with('/') && !remainder.contains("//");
+
+    match is_normalized {
+        true => Cow::Borrowed(remainder),
+        false => Cow::Owned(normalize_path(remainder)[1..].to_string()),
+    }
+}
+
+fn compile_pattern(pattern: &str) -> Result<Vec<Segment>, Error> {
+    let invalid_pattern = || Error::RouterError(RouterError::InvalidPattern(String::from(pattern)));
+
+    let pattern_without_slash = pattern.strip_prefix('/').ok_or_else(invalid_pattern)?;
+
+    let mut compiled_segments = Vec::new();
+    let mut segments = pattern_without_slash.split('/').peekable();
+
+    while let Some(segment) = segments.next() {
+        let compiled_segment = match segment.chars().next() {
+            Some(':') => match segment[1..].split_once('<') {
+                Some((name, constraint)) => {
+                    let constraint = match constraint {
+                        "int>" => Constraint::Int,
+                        "uuid>" => Constraint::Uuid,
+                        "slug>" => Constraint::Slug,
+                        _ => return Err(invalid_pattern()),
+                    };
+
+                    Segment::Param(String::from(name), Some(constraint))
+                }
+                None => Segment::Param(String::from(&segment[1..]), None),
+            },
+            Some('*') => {
+                if segments.peek().is_some() {
+                    return Err(invalid_pattern());
+                }
+
+                Segment::Wildcard(String::from(&segment[1..]))
+            }
+            _ => {
+                compiled_segments.push(Segment::Literal(String::from(segment)));
+                continue;
+            }
+        };
+
+        if let Segment::Param(name, _) | Segment::Wildcard(name) = &compiled_segment {
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(invalid_pattern());
+            }
+        }
+
+        compiled_segments.push(compiled_segment);
+    }
+
+    Ok(compiled_segments)
+}
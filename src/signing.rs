@@ -0,0 +1,44 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Appends the hex encoded HMAC-SHA256 signature of the value (`value.signature`). The name of the cookie is signed along with the value, so a signed value can't be moved to another cookie.
+pub(crate) fn sign(name: &str, value: &str, key: &[u8]) -> String {
+    let signature: String = mac(name, value, key)
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    format!("{}.{}", value, signature)
+}
+
+/// Returns the value of a signed value if its signature is valid, the signatures are compared in constant time.
+pub(crate) fn verify(name: &str, signed_value: &str, key: &[u8]) -> Option<String> {
+    let (value, signature) = signed_value.rsplit_once('.')?;
+
+    if signature.len() % 2 != 0 || !signature.is_ascii() {
+        return None;
+    }
+
+    let signature = (0..signature.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&signature[index..index + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    mac(name, value, key)
+        .verify_slice(&signature)
+        .ok()
+        .map(|_| String::from(value))
+}
+
+// Cookie names can't contain `=`, so `name=value` can't be forged by moving bytes between the name and the value
+fn mac(name: &str, value: &str, key: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(name.as_bytes());
+    mac.update(b"=");
+    mac.update(value.as_bytes());
+    mac
+}
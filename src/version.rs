@@ -0,0 +1,35 @@
+use std::fmt::Display;
+
+/// Version of the HTTP protocol used by a request or a response.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum HttpVersion {
+    /// `HTTP/1.0`
+    Http10,
+
+    /// `HTTP/1.1`, used by default
+    #[default]
+    Http11,
+
+    /// Any other `HTTP/` version, stored as it was received.
+    Other(String),
+}
+
+impl From<&str> for HttpVersion {
+    fn from(version_str: &str) -> Self {
+        match version_str {
+            "HTTP/1.0" => HttpVersion::Http10,
+            "HTTP/1.1" => HttpVersion::Http11,
+            _ => HttpVersion::Other(String::from(version_str)),
+        }
+    }
+}
+
+impl Display for HttpVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HttpVersion::Http10 => write!(f, "HTTP/1.0"),
+            HttpVersion::Http11 => write!(f, "HTTP/1.1"),
+            HttpVersion::Other(version) => write!(f, "{}", version),
+        }
+    }
+}
@@ -1,5 +1,14 @@
+use std::io::{BufReader, Cursor, ErrorKind, Read, Write};
+
+use servidor_http::etag::ETag;
 use servidor_http::package::Package;
-use servidor_http::request::{self, Method};
+use servidor_http::request::{
+    self, Auth, ByteRange, ChunkedReader, DigestAuth, EncodeSet, Method, Origin, ParseConfig,
+    ParseStatus, Query, QueryDecodeMode, ReaderConfig, Url,
+};
+use servidor_http::response::{Response, Status};
+use servidor_http::version::HttpVersion;
+use servidor_http::BinaryRepresentation;
 
 #[test]
 fn request_without_headers() {
@@ -67,7 +76,7 @@ macro_rules! generate_request_method_type_tests {
     };
 }
 
-generate_request_method_type_tests!(get_request, GET; post_request, POST; put_request, PUT; delete_request, DELETE; head_request, HEAD);
+generate_request_method_type_tests!(get_request, GET; post_request, POST; put_request, PUT; delete_request, DELETE; head_request, HEAD; patch_request, PATCH);
 
 #[test]
 fn request_with_invalid_method() {
@@ -124,3 +133,1502 @@ fn request_with_cookies() {
     assert_eq!(req.cookies.get("cookie1").unwrap(), "value1");
     assert_eq!(req.cookies.get("cookie2").unwrap(), "value2");
 }
+
+#[test]
+fn request_without_query_or_empty() {
+    let req = request::Request::try_from("GET /index.html HTTP/1.1\n").unwrap();
+
+    assert_eq!(req.query_or_empty().get("page"), None);
+
+    let req = request::Request::try_from("GET /index.html?page=2 HTTP/1.1\n").unwrap();
+
+    assert_eq!(req.query_or_empty().get("page").unwrap(), "2");
+}
+
+#[test]
+fn request_from_buf_read() {
+    let req_str = "POST /index.html HTTP/1.1\r\nContent-Length: 11\r\n\r\nHello world";
+    let mut reader = Cursor::new(req_str.as_bytes());
+
+    let req = request::Request::from_buf_read(&mut reader).unwrap();
+
+    assert_eq!(req.path.method, Method::POST);
+    assert_eq!(req.get_body_string(), "Hello world");
+}
+
+#[test]
+fn request_from_buf_read_partial_body() {
+    let req_str = "POST /index.html HTTP/1.1\r\nContent-Length: 20\r\n\r\nHello";
+    let mut reader = Cursor::new(req_str.as_bytes());
+
+    match request::Request::from_buf_read(&mut reader).unwrap_err() {
+        servidor_http::Error::Io(err) => assert_eq!(err.kind(), ErrorKind::UnexpectedEof),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn request_from_buf_read_leaves_next_request() {
+    let req_str = "POST /a HTTP/1.1\r\nContent-Length: 2\r\n\r\nokGET /b HTTP/1.1\r\n\r\n";
+    let mut reader = Cursor::new(req_str.as_bytes());
+
+    let first = request::Request::from_buf_read(&mut reader).unwrap();
+    let second = request::Request::from_buf_read(&mut reader).unwrap();
+
+    assert_eq!(first.get_body_string(), "ok");
+    assert_eq!(second.path.path, "/b");
+}
+
+#[test]
+fn request_with_method_override() {
+    let req_str = "POST /index.html HTTP/1.1\r\nX-HTTP-Method-Override: PATCH\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert_eq!(req.effective_method(true), Method::PATCH);
+    assert_eq!(req.effective_method(false), Method::POST);
+}
+
+#[test]
+fn request_with_method_override_form_field() {
+    let req_str = "POST /index.html HTTP/1.1\r\nContent-Type: application/x-www-form-urlencoded\r\n\r\n_method=DELETE";
+    let req = request::Request::try_from(req_str.as_bytes().to_vec()).unwrap();
+
+    assert_eq!(req.effective_method(true), Method::DELETE);
+}
+
+#[test]
+fn request_with_ignored_method_override() {
+    let req_str = "GET /index.html HTTP/1.1\r\nX-HTTP-Method-Override: PATCH\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert_eq!(req.effective_method(true), Method::GET);
+
+    let req_str = "POST /index.html HTTP/1.1\r\nX-HTTP-Method-Override: INVALID\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert_eq!(req.effective_method(true), Method::POST);
+}
+
+#[test]
+fn request_is_secure() {
+    let req_str = "GET /index.html HTTP/1.1\r\nX-Forwarded-Proto: https\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert!(req.is_secure(true));
+    assert!(!req.is_secure(false));
+
+    let req_str =
+        "GET /index.html HTTP/1.1\r\nForwarded: for=192.0.2.60;proto=https;by=203.0.113.43\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert!(req.is_secure(true));
+    assert!(!req.is_secure(false));
+
+    let req_str = "GET /index.html HTTP/1.1\r\nX-Forwarded-Proto: http\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert!(!req.is_secure(true));
+}
+
+#[test]
+fn request_with_origin() {
+    let req_str = "GET /index.html HTTP/1.1\r\nOrigin: https://app.example.com:8443\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert_eq!(
+        req.origin().unwrap(),
+        Origin::Tuple {
+            scheme: String::from("https"),
+            host: String::from("app.example.com"),
+            port: Some(8443),
+        }
+    );
+}
+
+#[test]
+fn request_with_null_origin() {
+    let req_str = "GET /index.html HTTP/1.1\r\nOrigin: null\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    let origin = req.origin().unwrap();
+
+    assert_eq!(origin, Origin::Null);
+    assert!(!origin.matches("null"));
+}
+
+#[test]
+fn request_same_origin() {
+    let req_str = "POST /index.html HTTP/1.1\r\nReferer: https://example.com/form?page=1\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    let origin = req.origin().unwrap();
+
+    assert!(origin.matches("https://example.com"));
+    assert!(origin.matches("https://example.com:443"));
+    assert!(!origin.matches("http://example.com"));
+    assert!(!origin.matches("https://evil.com"));
+}
+
+#[test]
+fn request_with_referer() {
+    let req_str =
+        "GET /index.html HTTP/1.1\r\nReferer: https://example.com:8080/search?q=rust#results\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert_eq!(
+        req.referer().unwrap(),
+        Url {
+            scheme: Some(String::from("https")),
+            host: Some(String::from("example.com:8080")),
+            path: String::from("/search"),
+            query: Some(String::from("q=rust")),
+        }
+    );
+}
+
+#[test]
+fn request_with_path_only_referer() {
+    let req_str = "GET /index.html HTTP/1.1\r\nReferer: /previous/page\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert_eq!(
+        req.referer().unwrap(),
+        Url {
+            scheme: None,
+            host: None,
+            path: String::from("/previous/page"),
+            query: None,
+        }
+    );
+}
+
+#[test]
+fn request_with_malformed_referer() {
+    let req_str = "GET /index.html HTTP/1.1\r\nReferer: not a url\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert_eq!(req.referer(), None);
+}
+
+#[test]
+fn request_with_space_before_header_colon() {
+    let req_str = "GET /index.html HTTP/1.1\r\nContent-Type : text/html\r\n\r\n";
+
+    let req =
+        request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::lenient()).unwrap();
+
+    assert_eq!(
+        req.get_header_list().get("Content-Type").unwrap(),
+        "text/html"
+    );
+
+    match request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::strict())
+        .unwrap_err()
+    {
+        servidor_http::Error::RequestError(request::RequestError::InvalidHeader(header)) => {
+            assert_eq!(header, "Content-Type : text/html")
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn iterate_query() {
+    let query = Query::try_from("page=2&sort=asc&limit=10").unwrap();
+
+    assert_eq!(query.len(), 3);
+    assert!(!query.is_empty());
+
+    let mut pairs: Vec<(&str, &str)> = Vec::new();
+    for (key, value) in &query {
+        pairs.push((key, value));
+    }
+    pairs.sort();
+
+    assert_eq!(pairs, vec![("limit", "10"), ("page", "2"), ("sort", "asc")]);
+    assert!(Query::new().is_empty());
+}
+
+#[test]
+fn request_with_if_match() {
+    let req_str = "PUT /doc HTTP/1.1\r\nIf-Match: \"v1\", W/\"v2\", \"v,3\"\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert_eq!(
+        req.if_match().unwrap(),
+        vec![
+            ETag::Strong(String::from("v1")),
+            ETag::Weak(String::from("v2")),
+            ETag::Strong(String::from("v,3")),
+        ]
+    );
+
+    assert!(req.if_match_passes(Some(&ETag::Strong(String::from("v1")))));
+}
+
+#[test]
+fn request_with_if_match_mismatch() {
+    let req_str = "DELETE /doc HTTP/1.1\r\nIf-Match: \"v1\"\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    let current = ETag::Strong(String::from("v2"));
+
+    assert!(!req.if_match_passes(Some(&current)));
+    assert!(!req.if_match_passes(None));
+
+    let response = Response::precondition_failed();
+    assert_eq!(response.status, Status::PreconditionFailed);
+}
+
+#[test]
+fn request_with_if_match_wildcard() {
+    let req_str = "PUT /doc HTTP/1.1\r\nIf-Match: *\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert_eq!(req.if_match().unwrap(), vec![ETag::Any]);
+    assert!(req.if_match_passes(Some(&ETag::Strong(String::from("v1")))));
+    assert!(!req.if_match_passes(None));
+}
+
+#[test]
+fn request_without_http_version() {
+    let req_str = "GET /health\r\n\r\n";
+
+    let req =
+        request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::lenient()).unwrap();
+
+    assert_eq!(req.path.path, "/health");
+    assert_eq!(req.version, HttpVersion::Http10);
+
+    match request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::strict())
+        .unwrap_err()
+    {
+        servidor_http::Error::RequestError(request::RequestError::InvalidRequest(_)) => (),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn request_edit_form() {
+    let req_str = "POST /submit HTTP/1.1\r\nContent-Type: application/x-www-form-urlencoded\r\nContent-Length: 27\r\n\r\nname=Juan&csrf_token=secret";
+    let mut req = request::Request::try_from(req_str.as_bytes().to_vec()).unwrap();
+
+    let form = req.form_mut().unwrap();
+    form.remove("csrf_token");
+    form.set("name", "Juan Pérez");
+
+    req.sync_form_to_body();
+
+    assert_eq!(req.get_body_string(), "name=Juan+P%C3%A9rez");
+    assert_eq!(req.get_header("Content-Length").unwrap(), "20");
+
+    let unparsed = request::Request::try_from(req_str.as_bytes().to_vec()).unwrap();
+    let mut parsed = unparsed.clone();
+    parsed.form_mut().unwrap();
+    assert_eq!(parsed, unparsed);
+
+    parsed.set_body(b"name=Ana".to_vec());
+    assert_eq!(parsed.form_mut().unwrap().get("name").unwrap(), "Ana");
+    assert_eq!(parsed.form_mut().unwrap().get("csrf_token"), None);
+}
+
+#[test]
+fn parse_method_lenient() {
+    let lenient = ParseConfig::lenient();
+
+    assert_eq!(Method::parse("get", &lenient).unwrap(), Method::GET);
+    assert_eq!(Method::parse(" POST ", &lenient).unwrap(), Method::POST);
+    assert_eq!(
+        Method::parse("FETCH", &lenient).unwrap(),
+        Method::Other(String::from("FETCH"))
+    );
+    assert!(Method::parse("FETCH", &ParseConfig::strict()).is_err());
+
+    let req = request::Request::try_from("get /index.html HTTP/1.1\n").unwrap();
+    assert_eq!(req.path.method, Method::GET);
+}
+
+#[test]
+fn parse_method_strict() {
+    let strict = ParseConfig::strict();
+
+    assert!(Method::parse("get", &strict).is_err());
+    assert!(Method::parse(" POST ", &strict).is_err());
+    assert!(Method::parse("FETCH", &strict).is_err());
+    assert_eq!(Method::parse("GET", &strict).unwrap(), Method::GET);
+}
+
+#[test]
+fn request_with_chunked_body() {
+    let req_str = "POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n6;ext=1\r\n world\r\n0\r\n\r\n";
+
+    let req = request::Request::try_from(req_str.as_bytes().to_vec()).unwrap();
+    assert_eq!(req.get_body_string(), "Hello world");
+
+    let req = request::Request::from_buf_read(&mut Cursor::new(req_str.as_bytes())).unwrap();
+    assert_eq!(req.get_body_string(), "Hello world");
+
+    for size in ["+3", "-3", "", "0x3"] {
+        let req_str = format!(
+            "POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n{}\r\nabc\r\n0\r\n\r\n",
+            size
+        );
+
+        match request::Request::try_from(req_str.as_bytes().to_vec()).unwrap_err() {
+            servidor_http::Error::RequestError(request::RequestError::InvalidChunk(_)) => (),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn request_with_oversized_chunk() {
+    let req_str = "POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\nFFFFFFFF\r\n";
+    let config = ParseConfig {
+        max_chunk_size: 1024,
+        ..ParseConfig::default()
+    };
+
+    match request::Request::from_buf_read_with_config(&mut Cursor::new(req_str.as_bytes()), &config)
+        .unwrap_err()
+    {
+        servidor_http::Error::RequestError(request::RequestError::BodyTooLarge(limit)) => {
+            assert_eq!(limit, 1024)
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn request_with_chunked_body_over_limit() {
+    let req_str =
+        "POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n5\r\nWorld\r\n0\r\n\r\n";
+    let config = ParseConfig {
+        max_body_size: 8,
+        ..ParseConfig::default()
+    };
+
+    match request::Request::parse_with_config(req_str.as_bytes(), &config).unwrap_err() {
+        servidor_http::Error::RequestError(request::RequestError::BodyTooLarge(limit)) => {
+            assert_eq!(limit, 8)
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn request_with_bytes_range() {
+    let req_str = "GET /video.mp4 HTTP/1.1\r\nRange: bytes=0-9, 20-, -5\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    let range = req.range().unwrap();
+
+    assert_eq!(range.unit, "bytes");
+    assert_eq!(
+        range.byte_ranges().unwrap(),
+        vec![
+            ByteRange::FromTo(0, 9),
+            ByteRange::From(20),
+            ByteRange::Suffix(5)
+        ]
+    );
+    assert_eq!(ByteRange::FromTo(0, 9).resolve(100), Some((0, 9)));
+    assert_eq!(ByteRange::Suffix(5).resolve(100), Some((95, 99)));
+    assert_eq!(ByteRange::From(200).resolve(100), None);
+}
+
+#[test]
+fn request_with_other_unit_range() {
+    let req_str = "GET /items HTTP/1.1\r\nRange: items=0-9\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    let range = req.range().unwrap();
+
+    assert_eq!(range.unit, "items");
+    assert_eq!(range.byte_ranges(), None);
+}
+
+#[test]
+fn request_header_names() {
+    let req_str =
+        "GET /index.html HTTP/1.1\r\nHost: example.com\r\nx-custom-header: 1\r\nACCEPT: */*\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    let mut names: Vec<&str> = req.header_names().collect();
+    names.sort();
+
+    assert_eq!(names, vec!["ACCEPT", "Host", "x-custom-header"]);
+}
+
+struct MockStream {
+    header: Cursor<Vec<u8>>,
+    body: Cursor<Vec<u8>>,
+    written: Vec<u8>,
+    written_before_body: Option<Vec<u8>>,
+}
+
+impl Read for MockStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let bytes_read = self.header.read(buf)?;
+
+        if bytes_read > 0 {
+            return Ok(bytes_read);
+        }
+
+        if self.written_before_body.is_none() {
+            self.written_before_body = Some(self.written.clone());
+        }
+
+        self.body.read(buf)
+    }
+}
+
+impl Write for MockStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.written.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn request_read_from_with_auto_continue() {
+    let mut stream = MockStream {
+        header: Cursor::new(
+            b"POST /upload HTTP/1.1\r\nExpect: 100-continue\r\nContent-Length: 4\r\n\r\n".to_vec(),
+        ),
+        body: Cursor::new(b"data".to_vec()),
+        written: Vec::new(),
+        written_before_body: None,
+    };
+
+    let req = request::Request::read_from(&mut stream, &ReaderConfig::default()).unwrap();
+
+    assert_eq!(req.get_body_string(), "data");
+    assert_eq!(
+        stream.written_before_body.unwrap(),
+        b"HTTP/1.1 100 Continue\r\n\r\n"
+    );
+}
+
+#[test]
+fn request_read_from_with_auto_continue_over_limit() {
+    let mut stream = MockStream {
+        header: Cursor::new(
+            b"POST /upload HTTP/1.1\r\nExpect: 100-continue\r\nContent-Length: 1024\r\n\r\n"
+                .to_vec(),
+        ),
+        body: Cursor::new(vec![0; 1024]),
+        written: Vec::new(),
+        written_before_body: None,
+    };
+
+    let config = ReaderConfig {
+        parse_config: ParseConfig {
+            max_body_size: 512,
+            ..ParseConfig::default()
+        },
+        ..ReaderConfig::default()
+    };
+
+    match request::Request::read_from(&mut stream, &config).unwrap_err() {
+        servidor_http::Error::RequestError(request::RequestError::BodyTooLarge(limit)) => {
+            assert_eq!(limit, 512)
+        }
+        _ => unreachable!(),
+    }
+    assert!(stream.written.is_empty());
+}
+
+#[test]
+fn request_read_from_without_auto_continue() {
+    let mut stream = MockStream {
+        header: Cursor::new(
+            b"POST /upload HTTP/1.1\r\nExpect: 100-continue\r\nContent-Length: 4\r\n\r\n".to_vec(),
+        ),
+        body: Cursor::new(b"data".to_vec()),
+        written: Vec::new(),
+        written_before_body: None,
+    };
+
+    let config = ReaderConfig {
+        auto_continue: false,
+        ..ReaderConfig::default()
+    };
+
+    let req = request::Request::read_from(&mut stream, &config).unwrap();
+
+    assert_eq!(req.get_body_string(), "data");
+    assert!(stream.written.is_empty());
+}
+
+#[test]
+fn request_copy_end_to_end_headers() {
+    let req_str = "GET /index.html HTTP/1.1\r\nHost: example.com\r\nAccept: */*\r\nConnection: keep-alive, X-Hop\r\nKeep-Alive: timeout=5\r\nTransfer-Encoding: chunked\r\nProxy-Authorization: Basic abc\r\nX-Hop: 1\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    let mut forwarded = request::Request::new(Method::GET, "/index.html", None);
+    forwarded.copy_end_to_end_headers_from(&req);
+
+    let mut names: Vec<&str> = forwarded.header_names().collect();
+    names.sort();
+
+    assert_eq!(names, vec!["Accept", "Host"]);
+}
+
+#[test]
+fn merge_query_defaults() {
+    let defaults = Query::try_from("sort=asc&limit=10").unwrap();
+    let mut query = Query::try_from("limit=50").unwrap();
+
+    query.merge(&defaults);
+
+    assert_eq!(query.len(), 2);
+    assert_eq!(query.get("sort").unwrap(), "asc");
+    assert_eq!(query.get("limit").unwrap(), "50");
+}
+
+#[test]
+fn request_with_te_header() {
+    let req_str = "GET /index.html HTTP/1.1\r\nTE: trailers, deflate;q=0.5\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert_eq!(
+        req.accepted_transfer_codings(),
+        vec![
+            (String::from("trailers"), 1.0),
+            (String::from("deflate"), 0.5)
+        ]
+    );
+    assert!(req.accepts_trailers());
+
+    let req = request::Request::try_from("GET /index.html HTTP/1.1\r\n").unwrap();
+
+    assert!(req.accepted_transfer_codings().is_empty());
+    assert!(!req.accepts_trailers());
+}
+
+#[test]
+fn request_with_conflicting_framing() {
+    let req_str = "POST /upload HTTP/1.1\r\nContent-Length: 5\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n0\r\n\r\n";
+
+    match request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::default())
+        .unwrap_err()
+    {
+        servidor_http::Error::RequestError(request::RequestError::ConflictingFraming(_)) => (),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn request_with_conflicting_content_lengths() {
+    let req_str = "POST /upload HTTP/1.1\r\nContent-Length: 5\r\ncontent-length: 6\r\n\r\nHello!";
+
+    match request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::default())
+        .unwrap_err()
+    {
+        servidor_http::Error::RequestError(request::RequestError::ConflictingFraming(_)) => (),
+        _ => unreachable!(),
+    }
+
+    let req_str = "POST /upload HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 5\r\n\r\nHello";
+    let request =
+        request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::default()).unwrap();
+
+    assert_eq!(request.get_body_string(), "Hello");
+}
+
+#[test]
+fn request_with_malformed_cookies() {
+    let req_str =
+        "GET /index.html HTTP/1.1\r\nCookie: session=abc; broken; =empty; theme=dark\r\n\r\n";
+
+    let req = request::Request::try_from(req_str).unwrap();
+
+    assert_eq!(req.cookies.get("session").unwrap(), "abc");
+    assert_eq!(req.cookies.get("theme").unwrap(), "dark");
+    assert!(!req.cookies.contains("broken"));
+
+    match request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::strict())
+        .unwrap_err()
+    {
+        servidor_http::Error::RequestError(request::RequestError::CookieError(pair)) => {
+            assert_eq!(pair, "broken")
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn request_with_extensions() {
+    #[derive(Debug, PartialEq)]
+    struct User(String);
+
+    let mut req = request::Request::try_from("GET /profile HTTP/1.1\r\n\r\n").unwrap();
+    let plain_req = req.clone();
+
+    req.extensions.insert(User(String::from("eugenio")));
+
+    assert_eq!(
+        req.extensions.get::<User>(),
+        Some(&User(String::from("eugenio")))
+    );
+    assert_eq!(req.extensions.get::<u32>(), None);
+    assert_eq!(req, plain_req);
+}
+
+#[test]
+fn request_line_with_extra_whitespace() {
+    let req_str = " GET  /index.html \tHTTP/1.1\r\nHost: example.com\r\n\r\n";
+
+    let req =
+        request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::default()).unwrap();
+
+    assert_eq!(req.path.method, Method::GET);
+    assert_eq!(req.path.path, "/index.html");
+    assert_eq!(req.version, HttpVersion::Http11);
+
+    match request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::strict())
+        .unwrap_err()
+    {
+        servidor_http::Error::RequestError(request::RequestError::InvalidRequest(_)) => (),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn request_with_swapped_headers() {
+    let mut req = request::Request::try_from(
+        "GET /index.html HTTP/1.1\r\nHost: example.com\r\nAccept: */*\r\n\r\n",
+    )
+    .unwrap();
+
+    let mut headers = req.take_headers();
+    assert_eq!(headers.len(), 2);
+    assert!(req.get_header_list().is_empty());
+
+    headers.clear();
+    headers.insert(String::from("X-Rewritten"), String::from("1"));
+    req.set_headers(headers);
+
+    assert_eq!(req.get_header("X-Rewritten").unwrap(), "1");
+    assert_eq!(req.get_header("Host"), None);
+    assert_eq!(req.get_header("Accept"), None);
+}
+
+#[test]
+fn request_effective_body_len() {
+    let req_str = "POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n6\r\n world\r\n0\r\n\r\n";
+    let req = request::Request::from_buf_read(&mut Cursor::new(req_str.as_bytes())).unwrap();
+
+    assert_eq!(req.get_header("Content-Length"), None);
+    assert_eq!(req.effective_body_len(), 11);
+
+    let req = request::Request::try_from("GET / HTTP/1.1\r\n\r\n").unwrap();
+    assert_eq!(req.effective_body_len(), 0);
+}
+
+#[test]
+fn query_with_too_many_params() {
+    let config = ParseConfig {
+        max_query_params: 3,
+        ..ParseConfig::default()
+    };
+
+    let query = Query::parse("a=1&b=2&c=3", &config).unwrap();
+    assert_eq!(query.len(), 3);
+
+    match Query::parse("a=1&b=2&c=3&d=4", &config).unwrap_err() {
+        servidor_http::Error::RequestError(request::RequestError::QueryError(_)) => (),
+        _ => unreachable!(),
+    }
+
+    let req_str = "GET /search?a=1&b=2&c=3&d=4 HTTP/1.1\r\n\r\n";
+    assert!(request::Request::parse_with_config(req_str.as_bytes(), &config).is_err());
+    assert!(
+        request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::default()).is_ok()
+    );
+}
+
+#[test]
+fn request_with_websocket_subprotocols() {
+    let req_str = "GET /chat HTTP/1.1\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Protocol: chat, superchat\r\n\r\n";
+    let req = request::Request::try_from(req_str).unwrap();
+
+    let protocols = req.websocket_subprotocols();
+    assert_eq!(protocols, vec!["chat", "superchat"]);
+
+    let mut response = Response::new(Status::SwitchingProtocol);
+    response.set_websocket_protocol(&protocols[0]);
+
+    assert!(response
+        .to_string()
+        .contains("Sec-WebSocket-Protocol: chat\r\n"));
+
+    let req = request::Request::try_from("GET /chat HTTP/1.1\r\n\r\n").unwrap();
+    assert!(req.websocket_subprotocols().is_empty());
+}
+
+#[test]
+fn request_with_invalid_content_length() {
+    for content_length in ["1 0", "+5", "5, 5", "0x5"] {
+        let req_str = format!(
+            "POST /upload HTTP/1.1\r\nContent-Length: {}\r\n\r\nHello",
+            content_length
+        );
+
+        match request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::default())
+            .unwrap_err()
+        {
+            servidor_http::Error::RequestError(request::RequestError::InvalidContentLength(
+                value,
+            )) => assert_eq!(value, content_length),
+            _ => unreachable!(),
+        }
+    }
+
+    let req_str = "POST /upload HTTP/1.1\r\nContent-Length:\t5 \r\n\r\nHello";
+    let req = request::Request::from_buf_read(&mut Cursor::new(req_str.as_bytes())).unwrap();
+
+    assert_eq!(req.get_body_string(), "Hello");
+}
+
+#[test]
+fn request_byte_lengths() {
+    let req_str = "POST /upload HTTP/1.1\r\nContent-Length: 5\r\n\r\nHello";
+
+    let req = request::Request::from_buf_read(&mut Cursor::new(req_str.as_bytes())).unwrap();
+    assert_eq!(req.header_byte_len(), req_str.len() - 5);
+    assert_eq!(req.body_byte_len(), 5);
+
+    let req_str =
+        "POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n0\r\n\r\n";
+
+    let req = request::Request::from_buf_read(&mut Cursor::new(req_str.as_bytes())).unwrap();
+    assert_eq!(req.header_byte_len() + req.body_byte_len(), req_str.len());
+    assert_eq!(req.effective_body_len(), 5);
+
+    let req =
+        request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::default()).unwrap();
+    assert_eq!(req.header_byte_len() + req.body_byte_len(), req_str.len());
+    assert_eq!(req.body_byte_len(), 15);
+}
+
+#[test]
+fn request_is_ajax() {
+    let req = request::Request::try_from(
+        "GET /items HTTP/1.1\r\nx-requested-with: xmlhttprequest\r\n\r\n",
+    )
+    .unwrap();
+    assert!(req.is_ajax());
+
+    let req = request::Request::try_from("GET /items HTTP/1.1\r\n\r\n").unwrap();
+    assert!(!req.is_ajax());
+}
+
+#[test]
+fn request_preferred_encoding() {
+    let with_accept_encoding = |accept_encoding: &str| {
+        request::Request::try_from(
+            format!(
+                "GET / HTTP/1.1\r\nAccept-Encoding: {}\r\n\r\n",
+                accept_encoding
+            )
+            .as_str(),
+        )
+        .unwrap()
+    };
+
+    let req = with_accept_encoding("gzip, br;q=0.9");
+    assert_eq!(
+        req.preferred_encoding(&["br", "gzip", "identity"]),
+        Some("gzip")
+    );
+    assert_eq!(
+        req.preferred_encoding(&["br", "identity"]),
+        Some("identity")
+    );
+    assert_eq!(req.preferred_encoding(&["deflate"]), None);
+
+    let req = with_accept_encoding("gzip;q=0.5, identity;q=0");
+    assert_eq!(req.preferred_encoding(&["identity", "gzip"]), Some("gzip"));
+    assert_eq!(req.preferred_encoding(&["identity"]), None);
+
+    let req = with_accept_encoding("br;q=0.2, *;q=0.8");
+    assert_eq!(req.preferred_encoding(&["br", "gzip"]), Some("gzip"));
+
+    let req = with_accept_encoding("*;q=0");
+    assert_eq!(req.preferred_encoding(&["gzip", "identity"]), None);
+
+    let req = request::Request::try_from("GET / HTTP/1.1\r\n\r\n").unwrap();
+    assert_eq!(req.preferred_encoding(&["br", "gzip"]), Some("br"));
+}
+
+#[test]
+fn encode_sets() {
+    let input = "/my files/a?b#c@d:e&f%20g";
+
+    assert_eq!(
+        EncodeSet::Path.encode(input),
+        "/my%20files/a%3Fb%23c@d:e&f%20g"
+    );
+    assert_eq!(
+        EncodeSet::Query.encode(input),
+        "/my%20files/a?b%23c@d:e&f%20g"
+    );
+    assert_eq!(
+        EncodeSet::Userinfo.encode(input),
+        "%2Fmy%20files%2Fa%3Fb%23c%40d:e&f%20g"
+    );
+}
+
+#[test]
+fn request_serialization() {
+    let mut req = request::Request::new(Method::GET, "/my files/report?.pdf", None);
+    req.add_header("Host", "example.com");
+
+    assert_eq!(
+        req.to_binary(),
+        b"GET /my%20files/report%3F.pdf HTTP/1.1\r\nHost: example.com\r\n\r\n"
+    );
+
+    let mut req = request::Request::new(
+        Method::POST,
+        "/search",
+        Some(Query::try_from("q=rust").unwrap()),
+    );
+    req.set_body(b"Hello".to_vec());
+
+    assert_eq!(
+        req.serialize_with(EncodeSet::Path),
+        b"POST /search?q=rust HTTP/1.1\r\n\r\nHello"
+    );
+}
+
+#[test]
+fn request_clone_without_body() {
+    let req_str =
+        "POST /upload?draft=1 HTTP/1.1\r\nContent-Length: 5\r\nCookie: session=abc\r\n\r\nHello";
+    let req =
+        request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::default()).unwrap();
+
+    let snapshot = req.clone_without_body();
+
+    assert_eq!(snapshot.get_body(), None);
+    assert_eq!(snapshot.get_header_list(), req.get_header_list());
+    assert_eq!(snapshot.path, req.path);
+    assert_eq!(snapshot.query, req.query);
+    assert_eq!(snapshot.cookies, req.cookies);
+    assert_eq!(req.get_body_string(), "Hello");
+}
+
+#[test]
+fn request_with_lf_boundary_before_crlf_boundary() {
+    let req_str =
+        "POST /upload HTTP/1.1\r\nX-Note: first\n\nsecond\r\nContent-Length: 9\r\n\r\nBody\n\ntwo";
+    let req =
+        request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::default()).unwrap();
+
+    assert_eq!(req.get_header("X-Note").unwrap(), "first");
+    assert_eq!(req.get_body_string(), "Body\n\ntwo");
+
+    let req_str = "POST /upload HTTP/1.1\nContent-Length: 4\n\nBody";
+    let req =
+        request::Request::parse_with_config(req_str.as_bytes(), &ParseConfig::default()).unwrap();
+
+    assert_eq!(req.get_body_string(), "Body");
+}
+
+#[test]
+fn all_methods() {
+    let methods = Method::all();
+
+    assert_eq!(methods.len(), 7);
+    assert_eq!(methods[0], Method::GET);
+
+    for method in methods {
+        assert_eq!(
+            &Method::try_from(method.to_string().as_str()).unwrap(),
+            method
+        );
+    }
+}
+
+#[test]
+fn request_with_preferences() {
+    let req = request::Request::try_from(
+        "PATCH /items/1 HTTP/1.1\r\nPrefer: return=minimal, wait=10, respond-async; foo=bar\r\n\r\n",
+    )
+    .unwrap();
+
+    let preferences = req.prefer();
+
+    assert_eq!(preferences.len(), 3);
+    assert_eq!(preferences.get("return").unwrap(), "minimal");
+    assert_eq!(preferences.get("wait").unwrap(), "10");
+    assert_eq!(preferences.get("respond-async").unwrap(), "");
+
+    let mut response = Response::new(Status::NoContent);
+    response.set_preference_applied(&[("return", preferences.get("return").unwrap())]);
+
+    assert_eq!(
+        response.get_header("Preference-Applied").unwrap(),
+        "return=minimal"
+    );
+
+    let req = request::Request::try_from("GET / HTTP/1.1\r\n\r\n").unwrap();
+    assert!(req.prefer().is_empty());
+}
+
+struct FailingStream {
+    sent: Cursor<Vec<u8>>,
+}
+
+impl Read for FailingStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.sent.read(buf)? {
+            0 => Err(std::io::Error::new(
+                ErrorKind::ConnectionReset,
+                "connection reset",
+            )),
+            bytes_read => Ok(bytes_read),
+        }
+    }
+}
+
+impl Write for FailingStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn request_read_error_surfaces_as_io_error() {
+    let mut stream = FailingStream {
+        sent: Cursor::new(b"POST /upload HTTP/1.1\r\nContent-Length: 10\r\n\r\nHel".to_vec()),
+    };
+
+    match request::Request::read_from(&mut stream, &ReaderConfig::default()).unwrap_err() {
+        servidor_http::Error::Io(err) => assert_eq!(err.kind(), ErrorKind::ConnectionReset),
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(feature = "signed-cookies")]
+#[test]
+fn request_with_signed_cookies() {
+    let key = b"secret key";
+
+    let mut response = Response::new(Status::OK);
+    response.set_signed_cookie("user", "eugenio", key);
+
+    let set_cookie = response.get_header("Set-Cookie").unwrap();
+    let cookie = set_cookie.split(';').next().unwrap();
+
+    let req = request::Request::try_from(
+        format!("GET / HTTP/1.1\r\nCookie: {}\r\n\r\n", cookie).as_str(),
+    )
+    .unwrap();
+
+    assert_eq!(req.signed_cookie("user", key).unwrap(), "eugenio");
+    assert_eq!(req.signed_cookie("user", b"other key"), None);
+
+    let tampered_cookie = cookie.replace("eugenio", "admin");
+    let req = request::Request::try_from(
+        format!("GET / HTTP/1.1\r\nCookie: {}\r\n\r\n", tampered_cookie).as_str(),
+    )
+    .unwrap();
+
+    assert_eq!(req.signed_cookie("user", key), None);
+
+    let renamed_cookie = cookie.replace("user=", "admin=");
+    let req = request::Request::try_from(
+        format!("GET / HTTP/1.1\r\nCookie: {}\r\n\r\n", renamed_cookie).as_str(),
+    )
+    .unwrap();
+
+    assert_eq!(req.signed_cookie("admin", key), None);
+}
+
+#[test]
+fn request_with_asterisk_form() {
+    let req =
+        request::Request::try_from("OPTIONS * HTTP/1.1\r\nHost: example.com\r\n\r\n").unwrap();
+
+    assert!(req.is_asterisk_form());
+    assert_eq!(req.path.path, "*");
+    assert_eq!(req.query, None);
+
+    let req = request::Request::try_from("OPTIONS /index.html HTTP/1.1\r\n\r\n").unwrap();
+    assert!(!req.is_asterisk_form());
+
+    match request::Request::try_from("GET * HTTP/1.1\r\n\r\n").unwrap_err() {
+        servidor_http::Error::RequestError(request::RequestError::InvalidRequest(_)) => (),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn request_parse_prefix() {
+    let config = ParseConfig::default();
+
+    assert_eq!(
+        request::Request::parse_prefix(b"POST /upload HTTP/1.1\r\nContent-Le", &config).unwrap(),
+        ParseStatus::Incomplete(None)
+    );
+
+    assert_eq!(
+        request::Request::parse_prefix(
+            b"POST /upload HTTP/1.1\r\nContent-Length: 10\r\n\r\nHel",
+            &config
+        )
+        .unwrap(),
+        ParseStatus::Incomplete(Some(7))
+    );
+
+    assert_eq!(
+        request::Request::parse_prefix(
+            b"POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHel",
+            &config
+        )
+        .unwrap(),
+        ParseStatus::Incomplete(None)
+    );
+
+    let data = b"POST /upload HTTP/1.1\r\nContent-Length: 5\r\n\r\nHelloGET / HTTP/1.1\r\n\r\n";
+
+    match request::Request::parse_prefix(data, &config).unwrap() {
+        ParseStatus::Complete(req, consumed) => {
+            assert_eq!(req.get_body_string(), "Hello");
+            assert_eq!(&data[consumed..], b"GET / HTTP/1.1\r\n\r\n");
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn query_decode_modes() {
+    let query = Query::try_from_with("a=b+c&d=e%2Bf", QueryDecodeMode::Form).unwrap();
+    assert_eq!(query.get("a").unwrap(), "b c");
+    assert_eq!(query.get("d").unwrap(), "e+f");
+
+    let query = Query::try_from_with("a=b+c&d=e%2Bf", QueryDecodeMode::Strict).unwrap();
+    assert_eq!(query.get("a").unwrap(), "b+c");
+    assert_eq!(query.get("d").unwrap(), "e+f");
+
+    assert_eq!(Query::try_from("a=b+c").unwrap().get("a").unwrap(), "b c");
+
+    let query = Query::try_from("q=rock%26roll&eq=a%3Db&bad=%zz").unwrap();
+    assert_eq!(query.len(), 3);
+    assert_eq!(query.get("q").unwrap(), "rock&roll");
+    assert_eq!(query.get("eq").unwrap(), "a=b");
+    assert_eq!(query.get("bad").unwrap(), "%zz");
+}
+
+#[test]
+fn request_path_segments() {
+    let req = request::Request::new(Method::GET, "/a//b/c/", None);
+
+    assert_eq!(req.path_segments().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+
+    let req = request::Request::new(Method::GET, "/", None);
+    assert_eq!(req.path_segments().count(), 0);
+}
+
+#[test]
+fn request_with_too_many_headers() {
+    let config = ParseConfig {
+        max_headers: 3,
+        ..ParseConfig::default()
+    };
+
+    let req_str = "GET / HTTP/1.1\r\nA: 1\r\nB: 2\r\nC: 3\r\n\r\n";
+    assert!(request::Request::parse_with_config(req_str.as_bytes(), &config).is_ok());
+
+    let req_str = "GET / HTTP/1.1\r\nA: 1\r\nB: 2\r\nC: 3\r\nD: 4\r\n\r\n";
+
+    match request::Request::parse_with_config(req_str.as_bytes(), &config).unwrap_err() {
+        servidor_http::Error::RequestError(request::RequestError::TooManyHeaders(limit)) => {
+            assert_eq!(limit, 3)
+        }
+        _ => unreachable!(),
+    }
+
+    let mut reader = Cursor::new(req_str.as_bytes());
+
+    match request::Request::from_buf_read_with_config(&mut reader, &config).unwrap_err() {
+        servidor_http::Error::RequestError(request::RequestError::TooManyHeaders(limit)) => {
+            assert_eq!(limit, 3)
+        }
+        _ => unreachable!(),
+    }
+    assert!(reader.position() < req_str.len() as u64);
+
+    let config = ParseConfig {
+        max_header_size: 32,
+        ..ParseConfig::default()
+    };
+
+    let mut reader = Cursor::new(b"GET / HTTP/1.1\r\nA: 1\r\n\r\n".to_vec());
+    assert!(request::Request::from_buf_read_with_config(&mut reader, &config).is_ok());
+
+    let mut reader = Cursor::new(format!("GET / HTTP/1.1\r\nA: {}", "a".repeat(1000)).into_bytes());
+
+    match request::Request::from_buf_read_with_config(&mut reader, &config).unwrap_err() {
+        servidor_http::Error::RequestError(request::RequestError::HeaderTooLarge(limit)) => {
+            assert_eq!(limit, 32)
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(reader.position(), 33);
+}
+
+#[test]
+fn request_with_digest_auth() {
+    let request = request::Request::try_from(
+        "GET /dir/index.html HTTP/1.1\r\nAuthorization: Digest username=\"Mufasa\", realm=\"testrealm@host.com\", nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", uri=\"/dir/index.html\", qop=auth, nc=00000001, cnonce=\"0a4f113b\", response=\"6629fae49393a05397450978507c4ef1\", opaque=\"5ccc069c403ebaf9f0171e9517f40e41\"\r\n\r\n",
+    )
+    .unwrap();
+
+    let digest = match request.auth().unwrap() {
+        Auth::Digest(digest) => digest,
+        _ => unreachable!(),
+    };
+
+    assert_eq!(digest.username, "Mufasa");
+    assert_eq!(digest.realm, "testrealm@host.com");
+    assert_eq!(digest.nonce, "dcd98b7102dd2f0e8b11d0f600bfb0c093");
+    assert_eq!(digest.uri, "/dir/index.html");
+    assert_eq!(digest.response, "6629fae49393a05397450978507c4ef1");
+    assert_eq!(digest.qop.as_deref(), Some("auth"));
+    assert_eq!(digest.nc.as_deref(), Some("00000001"));
+    assert_eq!(digest.cnonce.as_deref(), Some("0a4f113b"));
+
+    assert_eq!(
+        Auth::try_from("Digest username=\"a\\\"b\", realm=r, nonce=n, uri=/, response=x").unwrap(),
+        Auth::Digest(DigestAuth {
+            username: String::from("a\"b"),
+            realm: String::from("r"),
+            nonce: String::from("n"),
+            uri: String::from("/"),
+            response: String::from("x"),
+            ..DigestAuth::default()
+        })
+    );
+    assert!(Auth::try_from("Digest username=\"Mufasa, realm=r").is_err());
+    assert!(Auth::try_from("Digest username, realm=r, nonce=n, uri=/, response=x").is_err());
+    assert!(Auth::try_from("Digest username=\"a\"b, realm=r, nonce=n, uri=/, response=x").is_err());
+    assert!(Auth::try_from("Digest realm=r, nonce=n, uri=/, response=x").is_err());
+    assert_eq!(
+        Auth::try_from("Bearer abc").unwrap(),
+        Auth::Bearer(String::from("abc"))
+    );
+}
+
+#[test]
+fn request_built_fluently() {
+    let req = request::Request::new(Method::POST, "/login", None)
+        .with_header("Content-Type", "text/plain")
+        .with_header("Accept", "text/html")
+        .with_cookie("session", "abc")
+        .with_body(b"hello".to_vec());
+
+    assert_eq!(req.get_header("Content-Type").unwrap(), "text/plain");
+    assert_eq!(req.get_header("Accept").unwrap(), "text/html");
+    assert_eq!(req.cookies.get("session").unwrap(), "abc");
+    assert_eq!(req.get_body_string(), "hello");
+}
+
+#[test]
+fn query_with_valueless_keys() {
+    let req = request::Request::try_from("GET /search?verbose&page=2 HTTP/1.1\r\n\r\n").unwrap();
+    let query = req.query_or_empty();
+
+    assert!(query.has_flag("verbose"));
+    assert_eq!(query.get("verbose").unwrap(), "");
+    assert!(query.has_flag("page"));
+    assert_eq!(query.get("page").unwrap(), "2");
+    assert!(!query.has_flag("debug"));
+
+    let query = Query::try_from("a=1&&b").unwrap();
+    assert_eq!(query.len(), 2);
+    assert_eq!(query.get("b").unwrap(), "");
+}
+
+#[test]
+fn request_with_if_unmodified_since() {
+    let req = request::Request::try_from(
+        "PUT /doc HTTP/1.1\r\nIf-Unmodified-Since: Sun, 06 Nov 1994 08:49:37 GMT\r\n\r\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        req.if_unmodified_since(),
+        Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(784111777))
+    );
+
+    let req = request::Request::try_from(
+        "PUT /doc HTTP/1.1\r\nIf-Unmodified-Since: yesterday at noon\r\n\r\n",
+    )
+    .unwrap();
+
+    assert_eq!(req.if_unmodified_since(), None);
+    assert_eq!(
+        request::Request::new(Method::PUT, "/doc", None).if_unmodified_since(),
+        None
+    );
+
+    for (date, valid) in [
+        ("Thu, 29 Feb 2024 00:00:00 GMT", true),
+        ("Tue, 29 Feb 2000 00:00:00 GMT", true),
+        ("Mon, 29 Feb 2100 00:00:00 GMT", false),
+        ("Sat, 29 Feb 2023 00:00:00 GMT", false),
+        ("Sat, 31 Apr 2023 00:00:00 GMT", false),
+        ("Sun Feb 30 00:00:00 2020", false),
+        ("Sun, 06 Nov 300000000000 08:49:37 GMT", false),
+        ("Sun Nov  6 08:49:37 300000000000", false),
+        ("Sun, 06 Nov +1994 08:49:37 GMT", false),
+    ] {
+        let req = request::Request::try_from(
+            format!("PUT /doc HTTP/1.1\r\nIf-Unmodified-Since: {}\r\n\r\n", date).as_str(),
+        )
+        .unwrap();
+
+        assert_eq!(req.if_unmodified_since().is_some(), valid, "{}", date);
+    }
+}
+
+#[test]
+fn request_with_chunked_body_contradicting_declared_total() {
+    let data = b"POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\nContent-Length: 5\r\n\r\n";
+
+    match request::Request::parse_with_config(data, &ParseConfig::strict()).unwrap_err() {
+        servidor_http::Error::RequestError(request::RequestError::ConflictingFraming(_)) => (),
+        _ => unreachable!(),
+    }
+
+    let req = request::Request::parse_with_config(data, &ParseConfig::lenient()).unwrap();
+    assert_eq!(req.get_body_string(), "hello world");
+
+    let data = b"POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\nContent-Length: 11\r\n\r\n";
+    let req = request::Request::parse_with_config(data, &ParseConfig::strict()).unwrap();
+    assert_eq!(req.get_body_string(), "hello world");
+}
+
+#[test]
+fn request_with_extension_method() {
+    let req = request::Request::try_from("PROPFIND /docs HTTP/1.1\r\nDepth: 1\r\n\r\n").unwrap();
+
+    assert_eq!(req.path.method, Method::Other(String::from("PROPFIND")));
+    assert_eq!(req.path.method, Method::from("PROPFIND"));
+    assert_eq!(req.path.method.to_string(), "PROPFIND");
+    assert!(String::from_utf8(req.to_binary())
+        .unwrap()
+        .starts_with("PROPFIND /docs HTTP/1.1\r\n"));
+
+    for method in ["MKCOL", "COPY", "MOVE", "LOCK", "UNLOCK"] {
+        assert_eq!(
+            Method::parse(method, &ParseConfig::lenient()).unwrap(),
+            Method::Other(String::from(method))
+        );
+        assert!(Method::parse(method, &ParseConfig::strict()).is_err());
+    }
+
+    assert_eq!(
+        Method::parse("propfind", &ParseConfig::lenient()).unwrap(),
+        Method::from("PROPFIND")
+    );
+    assert!(Method::parse("PROP FIND", &ParseConfig::lenient()).is_err());
+    assert!(Method::parse("PROP\"FIND", &ParseConfig::lenient()).is_err());
+}
+
+#[test]
+fn query_with_boolean_values() {
+    for value in ["true", "1", "on", "yes", "TRUE", "On", "Yes"] {
+        let query = Query::try_from(format!("debug={}", value).as_str()).unwrap();
+        assert_eq!(query.get_bool("debug"), Some(true), "{}", value);
+    }
+
+    for value in ["false", "0", "off", "no", "False", "OFF", "NO"] {
+        let query = Query::try_from(format!("debug={}", value).as_str()).unwrap();
+        assert_eq!(query.get_bool("debug"), Some(false), "{}", value);
+    }
+
+    let query = Query::try_from("debug=maybe&verbose").unwrap();
+    assert_eq!(query.get_bool("debug"), None);
+    assert_eq!(query.get_bool("verbose"), None);
+    assert_eq!(query.get_bool("missing"), None);
+}
+
+#[test]
+fn request_with_multiple_host_headers() {
+    let data = b"GET / HTTP/1.1\r\nHost: example.com\r\nhost: evil.example\r\n\r\n";
+
+    match request::Request::parse_with_config(data, &ParseConfig::strict()).unwrap_err() {
+        servidor_http::Error::RequestError(request::RequestError::MultipleHostHeaders(2)) => (),
+        _ => unreachable!(),
+    }
+
+    assert!(request::Request::parse_with_config(data, &ParseConfig::lenient()).is_ok());
+    assert!(request::Request::parse_with_config(
+        b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n",
+        &ParseConfig::strict()
+    )
+    .is_ok());
+}
+
+#[test]
+fn request_accepts_media_types() {
+    let req = request::Request::try_from(
+        "GET / HTTP/1.1\r\nAccept: text/html, application/xml;q=0.9, */*;q=0.8\r\n\r\n",
+    )
+    .unwrap();
+    assert!(req.accepts("application/json"));
+    assert!(req.accepts("Text/HTML; charset=utf-8"));
+
+    let req =
+        request::Request::try_from("GET / HTTP/1.1\r\nAccept: */*, application/json;q=0\r\n\r\n")
+            .unwrap();
+    assert!(!req.accepts("application/json"));
+    assert!(req.accepts("text/plain"));
+
+    let req = request::Request::try_from("GET / HTTP/1.1\r\nAccept: image/*, text/*;q=0\r\n\r\n")
+        .unwrap();
+    assert!(req.accepts("image/png"));
+    assert!(!req.accepts("text/plain"));
+    assert!(!req.accepts("application/json"));
+
+    assert!(request::Request::new(Method::GET, "/", None).accepts("application/json"));
+}
+
+// Returns at most a few bytes on every read, like a slow connection
+struct TrickleReader {
+    data: Vec<u8>,
+    position: usize,
+}
+
+impl Read for TrickleReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(3).min(self.data.len() - self.position);
+        buf[..len].copy_from_slice(&self.data[self.position..self.position + len]);
+        self.position += len;
+
+        Ok(len)
+    }
+}
+
+#[test]
+fn chunked_body_read_incrementally() {
+    let upstream = TrickleReader {
+        data: b"5\r\nhello\r\n7;ext=1\r\n, world\r\n0\r\nExpires: never\r\n\r\nNEXT".to_vec(),
+        position: 0,
+    };
+    let mut reader = request::Request::chunked_body_reader(BufReader::new(upstream));
+
+    let mut body = Vec::new();
+    let mut buffer = [0; 2];
+
+    loop {
+        let bytes_read = reader.read(&mut buffer).unwrap();
+
+        if bytes_read == 0 {
+            break;
+        }
+
+        body.extend_from_slice(&buffer[..bytes_read]);
+    }
+
+    assert_eq!(body, b"hello, world");
+    assert_eq!(reader.read(&mut buffer).unwrap(), 0);
+
+    let mut next = String::new();
+    reader.into_inner().read_to_string(&mut next).unwrap();
+    assert_eq!(next, "NEXT");
+
+    let mut reader = request::Request::chunked_body_reader(Cursor::new(b"5\r\nhel".to_vec()));
+    let mut body = Vec::new();
+    assert_eq!(
+        reader.read_to_end(&mut body).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+
+    let mut reader = request::Request::chunked_body_reader(Cursor::new(b"zz\r\n".to_vec()));
+    assert_eq!(
+        reader.read_to_end(&mut body).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+
+    let config = ParseConfig {
+        max_chunk_size: 4,
+        ..ParseConfig::default()
+    };
+    let mut reader = ChunkedReader::with_config(Cursor::new(b"5\r\nhello\r\n".to_vec()), &config);
+    assert_eq!(
+        reader.read_to_end(&mut body).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn request_raw_path() {
+    let req =
+        request::Request::try_from("GET /my%20files/a%2Fb//c?x=%20 HTTP/1.1\r\n\r\n").unwrap();
+
+    assert_eq!(req.raw_path(), "/my%20files/a%2Fb//c");
+    assert_eq!(req.path.path, "/my files/a%2Fb//c");
+    assert_eq!(req.query_or_empty().get("x").unwrap(), " ");
+
+    let req = request::Request::new(Method::GET, "/index.html", None);
+    assert_eq!(req.raw_path(), "/index.html");
+}
+
+#[test]
+fn request_header_list() {
+    let req = request::Request::try_from(
+        "GET / HTTP/1.1\r\nAccept-Encoding: gzip, , br \r\nConnection: keep-alive\r\n\r\n",
+    )
+    .unwrap();
+
+    assert_eq!(req.header_list("accept-encoding"), vec!["gzip", "br"]);
+    assert_eq!(req.header_list("Connection"), vec!["keep-alive"]);
+    assert!(req.header_list("Vary").is_empty());
+}
+
+#[test]
+fn request_is_conditional() {
+    let req =
+        request::Request::try_from("GET /doc HTTP/1.1\r\nIf-None-Match: \"v1\"\r\n\r\n").unwrap();
+    assert!(req.is_conditional());
+
+    let req = request::Request::try_from(
+        "GET /doc HTTP/1.1\r\nif-modified-since: Sun, 06 Nov 1994 08:49:37 GMT\r\n\r\n",
+    )
+    .unwrap();
+    assert!(req.is_conditional());
+
+    let req = request::Request::try_from("GET /doc HTTP/1.1\r\nAccept: */*\r\n\r\n").unwrap();
+    assert!(!req.is_conditional());
+}
+
+#[test]
+fn request_raw_path_serialization() {
+    let req = request::Request::try_from("GET /a%2541/b%0A HTTP/1.1\r\n\r\n").unwrap();
+
+    assert_eq!(req.path.path, "/a%41/b%0A");
+    assert!(servidor_http::router::Route::try_new(Method::GET, &req.path.path).is_ok());
+    assert!(String::from_utf8(req.to_binary())
+        .unwrap()
+        .starts_with("GET /a%2541/b%0A HTTP/1.1\r\n"));
+
+    let mut req = request::Request::try_from("GET /old%20path HTTP/1.1\r\n\r\n").unwrap();
+    req.path.path = String::from("/new path");
+    assert!(String::from_utf8(req.to_binary())
+        .unwrap()
+        .starts_with("GET /new%20path HTTP/1.1\r\n"));
+}
@@ -1,5 +1,10 @@
-use servidor_http::response::Response;
-use servidor_http::response::{Package, Status};
+use servidor_http::response::{
+    IntoResponse, Negotiation, Package, RetryAfter, SseWriter, Status, UnmatchedPlaceholder,
+};
+use servidor_http::response::{Response, ResponseError};
+use servidor_http::version::HttpVersion;
+use servidor_http::BinaryRepresentation;
+use std::time::{Duration, UNIX_EPOCH};
 
 #[test]
 fn basic_response_to_string() {
@@ -38,7 +43,8 @@ fn response_with_body_to_string() {
     let mut response = Response::new(Status::OK);
     response.set_body_string(String::from("Hello, world!"));
 
-    let response_str = "HTTP/1.1 200 OK\r\n\r\nHello, world!";
+    let response_str =
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\n\r\nHello, world!";
     assert_eq!(response.to_string(), response_str);
 }
 
@@ -84,11 +90,1032 @@ fn response_with_cookies() {
 
 #[test]
 fn response_with_redirect() {
-    let mut response = Response::new(Status::Processing);
-    response.redirect("/test");
+    let mut response = Response::new(Status::OK);
+    response.redirect("/test").unwrap();
 
     let response_str = response.to_string();
 
     assert!(response_str.contains("HTTP/1.1 301 Moved Permanently"));
     assert!(response_str.contains("Location: /test"));
+
+    let mut response = Response::new(Status::Processing);
+
+    match response.redirect("/test").unwrap_err() {
+        servidor_http::Error::ResponseError(ResponseError::InvalidRedirectStatus(status)) => {
+            assert_eq!(status, Status::Processing)
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(response.status, Status::Processing);
+    assert_eq!(response.get_header("Location"), None);
+}
+
+#[test]
+fn response_without_reason_phrase() {
+    let mut response = Response::new(Status::OK);
+    response.omit_reason_phrase(true);
+
+    let response_str = response.to_string();
+
+    assert_eq!(response_str.lines().next().unwrap(), "HTTP/1.1 200");
+}
+
+#[test]
+fn response_with_retry_after_delay() {
+    let mut response = Response::new(Status::TooManyRequests);
+    response.set_retry_after(RetryAfter::Delay(Duration::from_secs(120)));
+
+    assert!(response.to_string().contains("Retry-After: 120\r\n"));
+}
+
+#[test]
+fn response_with_retry_after_date() {
+    let mut response = Response::new(Status::ServiceUnavailable);
+    response.set_retry_after(RetryAfter::Date(
+        UNIX_EPOCH + Duration::from_secs(784111777),
+    ));
+
+    assert!(response
+        .to_string()
+        .contains("Retry-After: Sun, 06 Nov 1994 08:49:37 GMT\r\n"));
+}
+
+#[test]
+fn response_with_redirect_status() {
+    let redirects = [
+        (Status::MovedPermanently, "HTTP/1.1 301 Moved Permanently"),
+        (Status::Found, "HTTP/1.1 302 Found"),
+        (Status::SeeOther, "HTTP/1.1 303 See Other"),
+        (Status::TemporaryRedirect, "HTTP/1.1 307 Temporary Redirect"),
+        (Status::PermanentRedirect, "HTTP/1.1 308 Permanent Redirect"),
+    ];
+
+    for (status, status_line) in redirects {
+        let mut response = Response::new(Status::OK);
+        response.redirect_with_status("/test", status).unwrap();
+
+        let response_str = response.to_string();
+
+        assert_eq!(response_str.lines().next().unwrap(), status_line);
+        assert!(response_str.contains("Location: /test"));
+    }
+}
+
+#[test]
+fn response_with_invalid_redirect_status() {
+    let mut response = Response::new(Status::OK);
+
+    match response
+        .redirect_with_status("/test", Status::OK)
+        .unwrap_err()
+    {
+        servidor_http::Error::ResponseError(ResponseError::InvalidRedirectStatus(status)) => {
+            assert_eq!(status, Status::OK)
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn response_with_body_string_content_type() {
+    let mut response = Response::new(Status::OK);
+    response.set_body_string(String::from("Hello, world!"));
+
+    assert_eq!(
+        response.get_header("Content-Type").unwrap(),
+        "text/plain; charset=utf-8"
+    );
+
+    let mut response = Response::new(Status::OK);
+    response.add_header("Content-Type", "text/html");
+    response.set_body_string(String::from("<h1>Hello, world!</h1>"));
+
+    assert_eq!(response.get_header("Content-Type").unwrap(), "text/html");
+
+    let mut response = Response::new(Status::OK);
+    response.set_body(b"raw".to_vec());
+
+    assert!(!response.has_header("Content-Type"));
+}
+
+#[test]
+fn response_without_body_status() {
+    for status in [Status::Continue, Status::NoContent, Status::NotModified] {
+        let mut response = Response::new(status.clone());
+        response.set_body(b"Should not be sent".to_vec());
+
+        let response_str = response.to_string();
+
+        assert_eq!(response_str, format!("HTTP/1.1 {}\r\n\r\n", status));
+        assert!(response.to_binary().ends_with(b"\r\n\r\n"));
+    }
+}
+
+#[test]
+fn response_with_informational_redirect_status() {
+    let mut response = Response::new(Status::OK);
+
+    assert!(response
+        .redirect_with_status("/test", Status::Continue)
+        .is_err());
+}
+
+#[test]
+fn response_with_duplicated_headers() {
+    let mut response = Response::new(Status::OK);
+    response.add_header("Content-Type", "text/plain");
+    response.add_header("content-type", "text/html");
+
+    let response_str = response.to_string();
+
+    assert_eq!(
+        response_str.to_lowercase().matches("content-type").count(),
+        1
+    );
+    assert!(response_str.contains("content-type: text/html\r\n"));
+}
+
+#[test]
+fn response_with_multiple_cookies() {
+    let mut response = Response::new(Status::OK);
+    response.set_session_cookie("first", "1");
+    response.set_session_cookie("second", "2");
+
+    let response_str = response.to_string();
+
+    assert!(response_str.contains("Set-Cookie: first=1; HttpOnly\r\n"));
+    assert!(response_str.contains("Set-Cookie: second=2; HttpOnly\r\n"));
+}
+
+#[test]
+fn response_remove_header() {
+    let mut response = Response::new(Status::OK);
+    response.add_header("Server", "Servidor HTTP");
+    response.remove_header("server");
+
+    assert!(!response.has_header("Server"));
+}
+
+#[test]
+fn response_with_into_body() {
+    let mut response = Response::new(Status::OK);
+    response.body(String::from("string"));
+    assert_eq!(response.get_body().unwrap(), b"string");
+
+    let mut response = Response::new(Status::OK);
+    response.body("str");
+    assert_eq!(response.get_body().unwrap(), b"str");
+    assert_eq!(
+        response.get_header("Content-Type").unwrap(),
+        "text/plain; charset=utf-8"
+    );
+
+    let mut response = Response::new(Status::OK);
+    response.body(vec![0u8, 1, 2]);
+    assert_eq!(response.get_body().unwrap(), vec![0u8, 1, 2]);
+    assert_eq!(
+        response.get_header("Content-Type").unwrap(),
+        "application/octet-stream"
+    );
+
+    let mut response = Response::new(Status::OK);
+    response
+        .body(&[3u8, 4][..])
+        .add_header("Server", "Servidor HTTP");
+    assert_eq!(response.get_body().unwrap(), vec![3u8, 4]);
+    assert!(response.has_header("Server"));
+    assert!(response.has_header("server"));
+
+    let mut response = Response::new(Status::OK);
+    response.add_header("content-type", "application/json");
+    response.set_body(b"{}".to_vec());
+
+    assert!(!response.for_head().to_string().contains("text/plain"));
+}
+
+#[test]
+fn response_with_ascii_attachment() {
+    let mut response = Response::new(Status::OK);
+    response.set_attachment("report.csv");
+
+    assert_eq!(
+        response.get_header("Content-Disposition").unwrap(),
+        "attachment; filename=\"report.csv\""
+    );
+}
+
+#[test]
+fn response_with_utf8_attachment() {
+    let mut response = Response::new(Status::OK);
+    response.set_attachment("informe año.csv");
+
+    assert_eq!(
+        response.get_header("Content-Disposition").unwrap(),
+        "attachment; filename=\"informe a_o.csv\"; filename*=UTF-8''informe%20a%C3%B1o.csv"
+    );
+}
+
+#[test]
+fn response_with_file_body() {
+    let path = std::env::temp_dir().join("servidor_http_file_body.txt");
+    std::fs::write(&path, "Streamed file content").unwrap();
+
+    let file = std::fs::File::open(&path).unwrap();
+    let len = file.metadata().unwrap().len();
+
+    let mut response = Response::new(Status::OK);
+    response.set_file_body(file, len).unwrap();
+
+    let mut output = Vec::new();
+    response.write_to(&mut output).unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+
+    let output_str = String::from_utf8(output).unwrap();
+
+    assert!(output_str.contains("Content-Length: 21\r\n"));
+    assert!(output_str.ends_with("\r\n\r\nStreamed file content"));
+}
+
+#[test]
+fn response_with_stream_body_http10() {
+    let mut response = Response::new(Status::OK);
+    response.version = HttpVersion::Http10;
+    response.add_header("Connection", "keep-alive");
+    response.set_stream_body(std::io::Cursor::new(b"Body until close".to_vec()));
+
+    let output = String::from_utf8(response.to_binary()).unwrap();
+
+    assert!(output.starts_with("HTTP/1.0 200 OK\r\n"));
+    assert!(output.contains("Connection: close\r\n"));
+    assert!(!output.contains("keep-alive"));
+    assert!(!output.contains("Transfer-Encoding"));
+    assert!(output.ends_with("\r\n\r\nBody until close"));
+}
+
+#[test]
+fn response_with_stream_body_http11() {
+    let mut response = Response::new(Status::OK);
+    response.set_stream_body(std::io::Cursor::new(b"Chunked".to_vec()));
+
+    let output = String::from_utf8(response.to_binary()).unwrap();
+
+    assert!(output.contains("Transfer-Encoding: chunked\r\n"));
+    assert!(output.ends_with("\r\n\r\n7\r\nChunked\r\n0\r\n\r\n"));
+}
+
+struct NotFoundError;
+
+impl From<NotFoundError> for Response {
+    fn from(_: NotFoundError) -> Self {
+        Response::new(Status::NotFound)
+    }
+}
+
+#[test]
+fn into_response_conversions() {
+    assert_eq!(
+        Status::NoContent.into_response(),
+        Response::new(Status::NoContent)
+    );
+
+    let response = (Status::Created, String::from("Created")).into_response();
+    assert_eq!(response.status, Status::Created);
+    assert_eq!(response.get_body().unwrap(), b"Created");
+
+    let mut expected = Response::new(Status::OK);
+    expected.add_header("Server", "Servidor HTTP");
+    assert_eq!(expected.clone().into_response(), expected);
+
+    let ok: Result<Response, NotFoundError> = Ok(expected.clone());
+    assert_eq!(ok.into_response(), expected);
+
+    let err: Result<Response, NotFoundError> = Err(NotFoundError);
+    assert_eq!(err.into_response(), Response::new(Status::NotFound));
+}
+
+#[test]
+fn response_with_vary() {
+    let mut response = Response::new(Status::OK);
+    response.add_vary("Accept-Encoding");
+    response.add_vary("accept-encoding");
+    response.add_vary("Accept-Language");
+
+    assert_eq!(
+        response.get_header("Vary").unwrap(),
+        "Accept-Encoding, Accept-Language"
+    );
+
+    response.add_vary("*");
+    response.add_vary("Accept");
+
+    assert_eq!(response.get_header("Vary").unwrap(), "*");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn response_with_json_body() {
+    let value = serde_json::json!({ "name": "servidor", "tags": ["http"] });
+
+    let mut compact = Response::new(Status::OK);
+    compact.set_json_body(&value).unwrap();
+
+    let compact_body = String::from_utf8(compact.get_body().unwrap()).unwrap();
+    assert_eq!(
+        compact.get_header("Content-Type").unwrap(),
+        "application/json"
+    );
+    assert!(!compact_body.contains('\n'));
+
+    let mut pretty = Response::new(Status::OK);
+    pretty.set_json_body_pretty(&value).unwrap();
+
+    let pretty_body = String::from_utf8(pretty.get_body().unwrap()).unwrap();
+    assert_eq!(
+        pretty.get_header("Content-Type").unwrap(),
+        "application/json"
+    );
+    assert!(pretty_body.contains("\n  \"name\": \"servidor\""));
+}
+
+#[test]
+fn response_with_range_for_head() {
+    let req = servidor_http::request::Request::try_from(
+        "HEAD /file.txt HTTP/1.1\r\nRange: bytes=0-4\r\n\r\n",
+    )
+    .unwrap();
+    let range = req.range().unwrap().byte_ranges().unwrap();
+
+    let mut response = Response::new(Status::OK);
+    response.body("Hello world");
+    response.set_range(&range[0]);
+
+    let head = response.for_head();
+    let head_str = head.to_string();
+
+    assert_eq!(head.status, Status::PartialContent);
+    assert_eq!(head.get_header("Content-Range").unwrap(), "bytes 0-4/11");
+    assert_eq!(head.get_header("Content-Length").unwrap(), "5");
+    assert!(head_str.ends_with("\r\n\r\n"));
+    assert!(head.to_binary().ends_with(b"\r\n\r\n"));
+}
+
+#[test]
+fn response_with_unsatisfiable_range() {
+    let mut response = Response::new(Status::OK);
+    response.body("Hello");
+    response.set_range(&servidor_http::request::ByteRange::From(10));
+
+    assert_eq!(response.status, Status::RequestedRangeNotSatisfiable);
+    assert_eq!(response.get_header("Content-Range").unwrap(), "bytes */5");
+    assert_eq!(response.get_body(), None);
+}
+
+#[test]
+fn response_with_range_of_file_body() {
+    let path = std::env::temp_dir().join("servidor_http_file_body_range.txt");
+    std::fs::write(&path, "Hello world").unwrap();
+
+    let mut response = Response::new(Status::OK);
+    response
+        .set_file_body(std::fs::File::open(&path).unwrap(), 11)
+        .unwrap();
+    response.set_range(&servidor_http::request::ByteRange::FromTo(6, 10));
+
+    assert_eq!(response.status, Status::PartialContent);
+    assert_eq!(
+        response.get_header("Content-Range").unwrap(),
+        "bytes 6-10/11"
+    );
+    assert!(response.to_bytes().unwrap().ends_with(b"\r\n\r\nworld"));
+    assert!(response
+        .for_head()
+        .to_string()
+        .contains("Content-Length: 5\r\n"));
+
+    response.set_range(&servidor_http::request::ByteRange::From(10));
+    assert_eq!(response.status, Status::RequestedRangeNotSatisfiable);
+    assert!(response.to_bytes().unwrap().ends_with(b"\r\n\r\n"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn response_from_error() {
+    let response = Response::from_error(Status::NotFound, "Page not found");
+    let body = String::from_utf8(response.get_body().unwrap()).unwrap();
+
+    assert_eq!(response.status, Status::NotFound);
+    assert_eq!(response.get_header("Content-Type").unwrap(), "text/html");
+    assert!(body.contains("<h1>404 Not Found</h1>"));
+    assert!(body.contains("<p>Page not found</p>"));
+
+    let response = Response::from_error(Status::BadRequest, "<script>alert(1)</script>");
+    let body = String::from_utf8(response.get_body().unwrap()).unwrap();
+
+    assert!(body.contains("<p>&lt;script&gt;alert(1)&lt;/script&gt;</p>"));
+    assert!(!body.contains("<script>"));
+}
+
+#[test]
+fn successful_status_lines() {
+    let statuses = [
+        (203, "Non-Authoritative Information"),
+        (205, "Reset Content"),
+        (206, "Partial Content"),
+        (207, "Multi-Status"),
+    ];
+
+    for (code, reason) in statuses {
+        let status = Status::from_code(code);
+
+        assert_eq!(status.code(), code);
+        assert_eq!(status.reason(), reason);
+        assert_eq!(Status::try_from(code).unwrap(), status);
+
+        let response = Response::new(status);
+        assert!(response
+            .to_string()
+            .starts_with(&format!("HTTP/1.1 {} {}\r\n", code, reason)));
+    }
+
+    assert_eq!(Status::from_code(206), Status::PartialContent);
+    assert_eq!(
+        Status::from_code(299),
+        Status::Other(299, String::from("Unknown"))
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn problem_response() {
+    let response = Response::problem(Status::NotFound, "Not Found", "No user with id 42");
+
+    assert_eq!(response.status, Status::NotFound);
+    assert_eq!(
+        response.get_header("Content-Type").unwrap(),
+        "application/problem+json"
+    );
+
+    let problem: serde_json::Value = serde_json::from_slice(&response.get_body().unwrap()).unwrap();
+
+    assert_eq!(problem["type"], "about:blank");
+    assert_eq!(problem["title"], "Not Found");
+    assert_eq!(problem["status"], 404);
+    assert_eq!(problem["detail"], "No user with id 42");
+}
+
+#[test]
+fn response_with_last_modified() {
+    let mut response = Response::new(Status::OK);
+    response.set_last_modified(UNIX_EPOCH + Duration::from_secs(784111777));
+
+    assert_eq!(
+        response.get_header("Last-Modified").unwrap(),
+        "Sun, 06 Nov 1994 08:49:37 GMT"
+    );
+}
+
+#[test]
+fn response_with_expires() {
+    let time = UNIX_EPOCH + Duration::from_secs(784111777);
+
+    let mut response = Response::new(Status::OK);
+    assert_eq!(response.expires(), None);
+
+    response.set_expires(time);
+    assert_eq!(
+        response.get_header("Expires").unwrap(),
+        "Sun, 06 Nov 1994 08:49:37 GMT"
+    );
+    assert_eq!(response.expires(), Some(time));
+
+    response.add_header("Expires", "Sun Nov  6 08:49:37 1994");
+    assert_eq!(response.expires(), Some(time));
+
+    response.add_header("Expires", "0");
+    assert_eq!(response.expires(), Some(UNIX_EPOCH));
+
+    response.add_header("Expires", "Sun, 06 Nov 300000000000 08:49:37 GMT");
+    assert_eq!(response.expires(), Some(UNIX_EPOCH));
+}
+
+#[test]
+fn server_sent_events() {
+    let response = Response::sse();
+
+    assert_eq!(
+        response.get_header("Content-Type").unwrap(),
+        "text/event-stream"
+    );
+    assert_eq!(response.get_header("Cache-Control").unwrap(), "no-cache");
+    assert_eq!(response.get_header("Connection").unwrap(), "keep-alive");
+
+    let mut writer = SseWriter::new(Vec::new());
+    writer
+        .send_event(Some("update"), "first line\nsecond line", Some("7"))
+        .unwrap();
+    writer.send_event(None, "ping", None).unwrap();
+
+    assert_eq!(
+        String::from_utf8(writer.into_inner()).unwrap(),
+        "event: update\ndata: first line\ndata: second line\nid: 7\n\ndata: ping\n\n"
+    );
+
+    let mut writer = SseWriter::new(Vec::new());
+    writer.send_event(None, "a\r\nb\rc\n", None).unwrap();
+
+    assert_eq!(
+        String::from_utf8(writer.into_inner()).unwrap(),
+        "data: a\ndata: b\ndata: c\ndata: \n\n"
+    );
+
+    let mut writer = SseWriter::new(Vec::new());
+    let error = writer
+        .send_event(Some("update\ndata: injected"), "ping", None)
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+
+    let error = writer.send_event(None, "ping", Some("7\0")).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(writer.into_inner().is_empty());
+}
+
+#[test]
+fn response_with_content_language() {
+    let mut response = Response::new(Status::OK);
+    response.set_content_language(&["en", "fr-CA"]).unwrap();
+
+    assert_eq!(
+        response.get_header("Content-Language").unwrap(),
+        "en, fr-CA"
+    );
+
+    for langs in [&["en", ""][..], &["en fr"], &[]] {
+        match response.set_content_language(langs).unwrap_err() {
+            servidor_http::Error::ResponseError(ResponseError::InvalidLanguageTag(_)) => (),
+            _ => unreachable!(),
+        }
+    }
+
+    assert_eq!(
+        response.get_header("Content-Language").unwrap(),
+        "en, fr-CA"
+    );
+}
+
+#[test]
+fn response_to_bytes_keeps_binary_body() {
+    let body = vec![0x89, b'P', b'N', b'G', 0xFF, 0x00, 0xFE];
+
+    let mut response = Response::new(Status::OK);
+    response.add_header("Content-Type", "image/png");
+    response.body(body.clone());
+
+    let bytes = response.to_bytes().unwrap();
+
+    assert!(bytes.starts_with(b"HTTP/1.1 200 OK\r\n"));
+    assert!(bytes.ends_with(&body));
+    assert_eq!(bytes, response.to_binary());
+    assert!(!response.to_string().as_bytes().ends_with(&body));
+}
+
+#[test]
+fn method_not_allowed_response() {
+    let response = Response::method_not_allowed(&[
+        servidor_http::request::Method::GET,
+        servidor_http::request::Method::POST,
+    ]);
+
+    assert_eq!(response.status, Status::MethodNotAllowed);
+    assert_eq!(response.get_header("Allow").unwrap(), "GET, POST");
+}
+
+#[test]
+fn early_hints_response() {
+    let response = Response::early_hints(&[
+        "</style.css>; rel=preload; as=style",
+        "</script.js>; rel=preload; as=script",
+    ]);
+
+    assert_eq!(
+        response.to_string(),
+        "HTTP/1.1 103 Early Hints\r\nLink: </style.css>; rel=preload; as=style\r\nLink: </script.js>; rel=preload; as=script\r\n\r\n"
+    );
+}
+
+#[test]
+fn response_piped_from_upstream() {
+    let mut upstream = Response::new(Status::Created);
+    upstream.add_header("Transfer-Encoding", "chunked");
+    upstream.add_header("Connection", "keep-alive, X-Upstream-Hop");
+    upstream.add_header("X-Upstream-Hop", "1");
+    upstream.add_header("Keep-Alive", "timeout=5");
+    upstream.add_header("Content-Type", "application/json");
+    upstream.set_body(b"{\"id\":1}".to_vec());
+
+    let response = Response::pipe_from(upstream);
+
+    assert_eq!(response.status, Status::Created);
+    assert_eq!(
+        response.get_header("Content-Type").unwrap(),
+        "application/json"
+    );
+    assert_eq!(response.get_header("Content-Length").unwrap(), "8");
+    assert_eq!(response.get_header("Transfer-Encoding"), None);
+    assert_eq!(response.get_header("Connection"), None);
+    assert_eq!(response.get_header("Keep-Alive"), None);
+    assert_eq!(response.get_header("X-Upstream-Hop"), None);
+    assert!(response
+        .to_bytes()
+        .unwrap()
+        .ends_with(b"\r\n\r\n{\"id\":1}"));
+}
+
+#[test]
+fn response_parsed_from_bytes() {
+    let response = Response::try_from(
+        b"HTTP/1.1 201 Created\r\nContent-Type: text/plain\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n0\r\n\r\n"
+            .to_vec(),
+    )
+    .unwrap();
+
+    assert_eq!(response.status, Status::Created);
+    assert_eq!(response.get_header("Content-Type").unwrap(), "text/plain");
+    assert_eq!(response.get_body().unwrap(), b"hello");
+
+    assert!(Response::try_from(b"HTTP/1.1 OK\r\n\r\n".to_vec()).is_err());
+    assert!(Response::try_from(b"HTTP/1.1 200 OK\r\nBroken header\r\n\r\n".to_vec()).is_err());
+
+    let response = Response::try_from(
+        b"HTTP/1.1 200 OK\r\nLink: </a>; rel=next\r\nLink: </b>; rel=prev\r\nContent-Length: 5\r\n\r\nhelloHTTP/1.1 200 OK"
+            .to_vec(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.get_header("Link").unwrap(),
+        "</a>; rel=next, </b>; rel=prev"
+    );
+    assert_eq!(response.get_body().unwrap(), b"hello");
+
+    assert!(
+        Response::try_from(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nhello".to_vec()).is_err()
+    );
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn response_with_gzip_body_decoded() {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    std::io::Write::write_all(&mut encoder, b"compressed hello").unwrap();
+    let gzip_body = encoder.finish().unwrap();
+
+    let mut data = format!(
+        "HTTP/1.1 200 OK\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\n\r\n",
+        gzip_body.len()
+    )
+    .into_bytes();
+    data.extend_from_slice(&gzip_body);
+
+    let response = Response::try_from(data).unwrap();
+
+    assert_eq!(response.get_body().unwrap(), gzip_body);
+    assert_eq!(response.decoded_body(1024).unwrap(), b"compressed hello");
+    assert_eq!(response.decoded_body(16).unwrap(), b"compressed hello");
+    match response.decoded_body(15).unwrap_err() {
+        servidor_http::Error::ResponseError(ResponseError::BodyTooLarge(15)) => (),
+        _ => unreachable!(),
+    }
+
+    let mut response = Response::new(Status::OK);
+    response.add_header("Content-Encoding", "zstd");
+    response.set_body(b"data".to_vec());
+
+    assert!(response.decoded_body(1024).is_err());
+}
+
+#[test]
+fn response_body_from_limited_reader() {
+    let mut response = Response::new(Status::OK);
+
+    response
+        .set_body_reader_limited(std::io::Cursor::new(b"small".to_vec()), 5)
+        .unwrap();
+    assert_eq!(response.get_body().unwrap(), b"small");
+
+    match response
+        .set_body_reader_limited(std::io::Cursor::new(vec![0; 1024]), 512)
+        .unwrap_err()
+    {
+        servidor_http::Error::ResponseError(ResponseError::BodyTooLarge(512)) => (),
+        _ => unreachable!(),
+    }
+    assert_eq!(response.get_body().unwrap(), b"small");
+}
+
+#[test]
+fn response_with_prefixed_cookies() {
+    let mut response = Response::new(Status::OK);
+
+    match response
+        .set_cookie("__Host-session", "abc", &["Path=/", "HttpOnly"])
+        .unwrap_err()
+    {
+        servidor_http::Error::ResponseError(ResponseError::InvalidCookiePrefix(name)) => {
+            assert_eq!(name, "__Host-session")
+        }
+        _ => unreachable!(),
+    }
+    assert!(response
+        .set_cookie(
+            "__Host-session",
+            "abc",
+            &["Secure", "Path=/", "Domain=example.com"]
+        )
+        .is_err());
+    assert!(response
+        .set_cookie("__Host-session", "abc", &["Secure", "Path=/app"])
+        .is_err());
+    assert!(response
+        .set_cookie("__Secure-id", "1", &["HttpOnly"])
+        .is_err());
+    assert_eq!(response.get_header("Set-Cookie"), None);
+
+    response
+        .set_cookie("__Host-session", "abc", &["Secure", "Path=/", "HttpOnly"])
+        .unwrap();
+    response
+        .set_cookie("__Secure-id", "1", &["Secure"])
+        .unwrap();
+    response.set_cookie("theme", "dark", &[]).unwrap();
+
+    assert_eq!(
+        response.get_header("Set-Cookie").unwrap(),
+        "__Host-session=abc; Secure; Path=/; HttpOnly\n__Secure-id=1; Secure\ntheme=dark"
+    );
+}
+
+#[test]
+fn status_parsed_from_status_line() {
+    assert_eq!(
+        "HTTP/1.1 404 Not Found".parse::<Status>().unwrap(),
+        Status::NotFound
+    );
+    assert_eq!("200 OK".parse::<Status>().unwrap(), Status::OK);
+    assert_eq!(
+        "HTTP/1.1 599 Network Connect Timeout Error"
+            .parse::<Status>()
+            .unwrap(),
+        Status::Other(599, String::from("Network Connect Timeout Error"))
+    );
+    assert_eq!(
+        "HTTP/1.1 599".parse::<Status>().unwrap(),
+        Status::Other(599, String::from("Unknown"))
+    );
+
+    match "HTTP/1.1 OK".parse::<Status>().unwrap_err() {
+        servidor_http::Error::ResponseError(ResponseError::InvalidStatusLine(line)) => {
+            assert_eq!(line, "HTTP/1.1 OK")
+        }
+        _ => unreachable!(),
+    }
+    assert!("1000 Too Big".parse::<Status>().is_err());
+
+    let response = Response::try_from(b"HTTP/1.1 299 Custom Reason\r\n\r\n".to_vec()).unwrap();
+    assert_eq!(response.status.reason(), "Custom Reason");
+}
+
+#[test]
+fn response_header_set_if_absent() {
+    let mut response = Response::new(Status::OK);
+    response.add_header("content-type", "application/json");
+
+    response.set_header_if_absent("Content-Type", "text/plain");
+    response.set_header_if_absent("X-Frame-Options", "DENY");
+
+    assert_eq!(
+        response.get_header("Content-Type").unwrap(),
+        "application/json"
+    );
+    assert_eq!(response.get_header("X-Frame-Options").unwrap(), "DENY");
+    assert_eq!(response.get_header_list().len(), 2);
+}
+
+#[test]
+fn response_range_or_full_with_if_range() {
+    let etag = servidor_http::etag::ETag::Strong(String::from("v2"));
+    let modified = UNIX_EPOCH + Duration::from_secs(784111777);
+
+    let request = servidor_http::request::Request::try_from(
+        "GET /file HTTP/1.1\r\nRange: bytes=0-4\r\nIf-Range: \"v2\"\r\n\r\n",
+    )
+    .unwrap();
+    let response = Response::range_or_full(b"hello world".to_vec(), &request, Some(&etag), None);
+
+    assert_eq!(response.status, Status::PartialContent);
+    assert_eq!(
+        response.get_header("Content-Range").unwrap(),
+        "bytes 0-4/11"
+    );
+    assert_eq!(response.get_body().unwrap(), b"hello");
+
+    let request = servidor_http::request::Request::try_from(
+        "GET /file HTTP/1.1\r\nRange: bytes=0-4\r\nIf-Range: \"v1\"\r\n\r\n",
+    )
+    .unwrap();
+    let response = Response::range_or_full(b"hello world".to_vec(), &request, Some(&etag), None);
+
+    assert_eq!(response.status, Status::OK);
+    assert_eq!(response.get_header("Content-Range"), None);
+    assert_eq!(response.get_body().unwrap(), b"hello world");
+    assert_eq!(response.get_header("ETag").unwrap(), "\"v2\"");
+
+    let request = servidor_http::request::Request::try_from(
+        "GET /file HTTP/1.1\r\nRange: bytes=6-\r\nIf-Range: Sun, 06 Nov 1994 08:49:37 GMT\r\n\r\n",
+    )
+    .unwrap();
+    let response = Response::range_or_full(b"hello world".to_vec(), &request, None, Some(modified));
+    assert_eq!(response.get_body().unwrap(), b"world");
+
+    let response = Response::range_or_full(
+        b"hello world".to_vec(),
+        &request,
+        None,
+        Some(modified + Duration::from_secs(60)),
+    );
+    assert_eq!(response.status, Status::OK);
+    assert_eq!(response.get_body().unwrap(), b"hello world");
+}
+
+#[test]
+fn response_body_len() {
+    let mut response = Response::new(Status::OK);
+    assert_eq!(response.body_len(), 0);
+    assert!(response.body_is_empty());
+
+    response.set_body_string(String::from("¡Hola, señor!"));
+    assert_eq!(response.body_len(), "¡Hola, señor!".len());
+    assert_eq!(response.body_len(), 15);
+    assert!(!response.body_is_empty());
+
+    response.set_stream_body(std::io::Cursor::new(b"streamed".to_vec()));
+    assert_eq!(response.body_len(), 0);
+    response.add_header("Content-Length", "8");
+    assert_eq!(response.body_len(), 8);
+}
+
+#[test]
+fn response_with_template_body() {
+    let vars = std::collections::HashMap::from([("name", "<b>Ana</b>"), ("count", "3")]);
+
+    let mut response = Response::new(Status::OK);
+    response.set_body_template(
+        "<p>Hello {{name}}, you have {{ count }} messages{{missing}}</p>",
+        &vars,
+    );
+
+    assert_eq!(response.get_header("Content-Type").unwrap(), "text/html");
+    assert_eq!(
+        response.get_body().unwrap(),
+        b"<p>Hello &lt;b&gt;Ana&lt;/b&gt;, you have 3 messages{{missing}}</p>"
+    );
+
+    response.set_body_template_with(
+        "{{count}}{{missing}} {{unclosed",
+        &vars,
+        UnmatchedPlaceholder::Blank,
+    );
+    assert_eq!(response.get_body().unwrap(), b"3 {{unclosed");
+}
+
+#[test]
+fn response_with_keep_alive_params() {
+    let mut response = Response::new(Status::OK);
+    response.version = HttpVersion::Http10;
+    response.set_keep_alive_params(5, 100);
+
+    assert_eq!(response.get_header("Connection").unwrap(), "keep-alive");
+    assert_eq!(
+        response.get_header("Keep-Alive").unwrap(),
+        "timeout=5, max=100"
+    );
+    assert!(response
+        .to_string()
+        .contains("Keep-Alive: timeout=5, max=100\r\n"));
+}
+
+#[test]
+fn response_vary_by_negotiation() {
+    let request = servidor_http::request::Request::try_from(
+        "GET / HTTP/1.1\r\nAccept-Encoding: br, gzip\r\nAccept-Language: es\r\n\r\n",
+    )
+    .unwrap();
+
+    let mut response = Response::new(Status::OK);
+    let encoding = request.preferred_encoding(&["gzip", "br"]).unwrap();
+    response.add_header("Content-Encoding", encoding);
+    response.add_header("Content-Language", "es");
+
+    response.vary_by_negotiation(&[Negotiation::Encoding, Negotiation::Language]);
+    assert_eq!(
+        response.get_header("Vary").unwrap(),
+        "Accept-Encoding, Accept-Language"
+    );
+
+    response.vary_by_negotiation(&[Negotiation::Encoding, Negotiation::ContentType]);
+    assert_eq!(
+        response.get_header("Vary").unwrap(),
+        "Accept-Encoding, Accept-Language, Accept"
+    );
+}
+
+#[test]
+fn response_with_appended_body() {
+    let mut response = Response::new(Status::OK);
+
+    response.append_body_str("<header></header>");
+    response.append_body(b"<main></main>");
+    response.append_body_str("<footer></footer>");
+
+    assert_eq!(
+        response.get_body().unwrap(),
+        b"<header></header><main></main><footer></footer>"
+    );
+    assert!(response
+        .for_head()
+        .to_string()
+        .contains("Content-Length: 47\r\n"));
+
+    let path = std::env::temp_dir().join("servidor_http_file_body_appended.txt");
+    std::fs::write(&path, "File content").unwrap();
+
+    let mut response = Response::new(Status::OK);
+    response
+        .set_file_body(std::fs::File::open(&path).unwrap(), 12)
+        .unwrap();
+    response.append_body(b"appended");
+
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(response.body_len(), 8);
+    assert_eq!(response.get_body().unwrap(), b"appended");
+    assert!(response.to_bytes().unwrap().ends_with(b"\r\n\r\nappended"));
+    assert!(response
+        .for_head()
+        .to_string()
+        .contains("Content-Length: 8\r\n"));
+}
+
+#[test]
+fn response_range_or_full_with_wednesday_if_range() {
+    let modified = UNIX_EPOCH + Duration::from_secs(784382400);
+
+    let request = servidor_http::request::Request::try_from(
+        "GET /file HTTP/1.1\r\nRange: bytes=0-4\r\nIf-Range: Wed, 09 Nov 1994 12:00:00 GMT\r\n\r\n",
+    )
+    .unwrap();
+    let response = Response::range_or_full(b"hello world".to_vec(), &request, None, Some(modified));
+
+    assert_eq!(response.status, Status::PartialContent);
+    assert_eq!(response.get_body().unwrap(), b"hello");
+}
+
+#[test]
+fn response_file_body_replaced_and_cloned() {
+    let path = std::env::temp_dir().join("servidor_http_file_body_clones.txt");
+    std::fs::write(&path, "File content").unwrap();
+
+    let mut response = Response::new(Status::OK);
+    response
+        .set_file_body(std::fs::File::open(&path).unwrap(), 12)
+        .unwrap();
+
+    let clone = response.clone();
+    assert!(response
+        .to_bytes()
+        .unwrap()
+        .ends_with(b"\r\n\r\nFile content"));
+    assert!(clone.to_bytes().unwrap().ends_with(b"\r\n\r\nFile content"));
+    assert!(response
+        .to_bytes()
+        .unwrap()
+        .ends_with(b"\r\n\r\nFile content"));
+
+    let mut truncated = Response::new(Status::OK);
+    truncated
+        .set_file_body(std::fs::File::open(&path).unwrap(), 100)
+        .unwrap();
+    assert!(truncated.to_bytes().is_err());
+    assert!(truncated.to_binary().is_empty());
+
+    std::fs::remove_file(&path).unwrap();
+
+    response.set_body(b"In memory".to_vec());
+    assert_eq!(response.body_len(), 9);
+    assert!(response.to_bytes().unwrap().ends_with(b"\r\n\r\nIn memory"));
+    assert!(response
+        .for_head()
+        .to_string()
+        .contains("Content-Length: 9\r\n"));
 }
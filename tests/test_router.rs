@@ -0,0 +1,343 @@
+use servidor_http::request::{Method, Request};
+use servidor_http::response::{Package, Response, Status};
+use servidor_http::router::{self, Middleware, Route, RouteTable};
+
+#[test]
+fn route_table_from_pairs() {
+    let table = RouteTable::from_pairs(&[
+        (Method::GET, "/a"),
+        (Method::POST, "/b"),
+        (Method::GET, "/users/:id"),
+        (Method::GET, "/static/*path"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        table.export(),
+        vec![
+            Route::new(Method::GET, "/a"),
+            Route::new(Method::POST, "/b"),
+            Route::new(Method::GET, "/users/:id"),
+            Route::new(Method::GET, "/static/*path"),
+        ]
+    );
+}
+
+#[test]
+fn route_table_malformed_pattern() {
+    for pattern in ["/:", "/*", "/*path/after", "no-slash"] {
+        match RouteTable::from_pairs(&[(Method::GET, pattern)]).unwrap_err() {
+            servidor_http::Error::RouterError(router::RouterError::InvalidPattern(p)) => {
+                assert_eq!(p, pattern)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn hello_handler(_: Request, mut res: Response) -> Response {
+    res.set_body_string(String::from("Hello"));
+    res
+}
+
+#[test]
+fn route_table_resolve_params() {
+    let mut table = RouteTable::new();
+    table
+        .handle_route(Route::new(Method::GET, "/users/:id"), hello_handler)
+        .unwrap();
+
+    let route_match = table
+        .resolve(&Route::new(Method::GET, "/users/42"))
+        .unwrap();
+
+    assert_eq!(route_match.params.get("id").unwrap(), "42");
+    assert!(!route_match.head_fallback);
+    assert!(route_match.handler.is_some());
+}
+
+#[test]
+fn route_table_head_fallback() {
+    let mut table = RouteTable::new();
+    table
+        .handle_route(Route::new(Method::GET, "/index"), hello_handler)
+        .unwrap();
+
+    let route_match = table.resolve(&Route::new(Method::HEAD, "/index")).unwrap();
+
+    assert_eq!(route_match.route, Route::new(Method::GET, "/index"));
+    assert!(route_match.head_fallback);
+
+    match table
+        .resolve(&Route::new(Method::HEAD, "/missing"))
+        .unwrap_err()
+    {
+        servidor_http::Error::RouterError(router::RouterError::RouteNotFound(_)) => (),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn route_table_explicit_head_route() {
+    let mut table = RouteTable::new();
+    table
+        .handle_route(Route::new(Method::GET, "/index"), hello_handler)
+        .unwrap();
+    table
+        .handle_route(Route::new(Method::HEAD, "/index"), hello_handler)
+        .unwrap();
+
+    let route_match = table.resolve(&Route::new(Method::HEAD, "/index")).unwrap();
+
+    assert!(!route_match.head_fallback);
+}
+
+#[test]
+fn route_table_compiled_patterns() {
+    let table = RouteTable::from_pairs(&[
+        (Method::GET, "/"),
+        (Method::GET, "/users/:id/posts/:post"),
+        (Method::GET, "/static/*path"),
+    ])
+    .unwrap();
+
+    assert!(table.resolve(&Route::new(Method::GET, "/")).is_ok());
+    assert!(table.resolve(&Route::new(Method::GET, "/users/1")).is_err());
+    assert!(table.resolve(&Route::new(Method::POST, "/")).is_err());
+
+    let route_match = table
+        .resolve(&Route::new(Method::GET, "/users/1/posts/2"))
+        .unwrap();
+    assert_eq!(route_match.params.get("id").unwrap(), "1");
+    assert_eq!(route_match.params.get("post").unwrap(), "2");
+
+    let route_match = table
+        .resolve(&Route::new(Method::GET, "/static/css/main.css"))
+        .unwrap();
+    assert_eq!(route_match.params.get("path").unwrap(), "css/main.css");
+}
+
+#[test]
+fn route_table_wildcard_normalization() {
+    let table = RouteTable::from_pairs(&[(Method::GET, "/static/*path")]).unwrap();
+
+    for path in ["/static//a/b/", "/static/a//b", "/static/a/b//"] {
+        let route_match = table.resolve(&Route::new(Method::GET, path)).unwrap();
+
+        assert_eq!(route_match.params.get("path").unwrap(), "a/b");
+    }
+
+    let route_match = table.resolve(&Route::new(Method::GET, "/static/")).unwrap();
+    assert_eq!(route_match.params.get("path").unwrap(), "");
+}
+
+#[test]
+fn normalize_paths() {
+    assert_eq!(router::normalize_path("/a//b/"), "/a/b");
+    assert_eq!(router::normalize_path("//"), "/");
+    assert_eq!(router::normalize_path(""), "/");
+}
+
+struct PoweredBy;
+
+impl Middleware for PoweredBy {
+    fn handle(&self, request: Request, next: &dyn Fn(Request) -> Response) -> Response {
+        let mut response = next(request);
+        response.add_header("X-Powered-By", "servidor_http");
+        response
+    }
+}
+
+struct RequireAuth;
+
+impl Middleware for RequireAuth {
+    fn handle(&self, request: Request, next: &dyn Fn(Request) -> Response) -> Response {
+        match request.get_header("Authorization") {
+            Some(_) => next(request),
+            None => Response::new(Status::Unauthorized),
+        }
+    }
+}
+
+#[test]
+fn route_table_middlewares() {
+    let mut table = RouteTable::new();
+    table
+        .handle_route(Route::new(Method::GET, "/hello"), hello_handler)
+        .unwrap();
+    table.add_middleware(PoweredBy);
+    table.add_middleware(RequireAuth);
+
+    let request =
+        Request::try_from("GET /hello HTTP/1.1\r\nAuthorization: Bearer abc\r\n\r\n").unwrap();
+    let response = table.dispatch(request).unwrap();
+
+    assert_eq!(response.status, Status::OK);
+    assert_eq!(response.get_body().unwrap(), b"Hello");
+    assert_eq!(
+        response.get_header("X-Powered-By").unwrap(),
+        "servidor_http"
+    );
+
+    let request = Request::try_from("GET /hello HTTP/1.1\r\n\r\n").unwrap();
+    let response = table.dispatch(request).unwrap();
+
+    assert_eq!(response.status, Status::Unauthorized);
+    assert_eq!(response.get_body(), None);
+    assert_eq!(
+        response.get_header("X-Powered-By").unwrap(),
+        "servidor_http"
+    );
+
+    let request = Request::try_from("DELETE /hello HTTP/1.1\r\n\r\n").unwrap();
+
+    match table.dispatch(request).unwrap_err() {
+        servidor_http::Error::RouterError(router::RouterError::MethodNotAllowed(methods)) => {
+            assert_eq!(methods, vec![Method::GET, Method::HEAD])
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn route_table_param_constraints() {
+    let table = RouteTable::from_pairs(&[
+        (Method::GET, "/users/:id<int>"),
+        (Method::GET, "/users/:name<slug>"),
+        (Method::GET, "/orders/:id<uuid>"),
+    ])
+    .unwrap();
+
+    let route_match = table
+        .resolve(&Route::new(Method::GET, "/users/42"))
+        .unwrap();
+    assert_eq!(route_match.route.path, "/users/:id<int>");
+    assert_eq!(route_match.params.get("id").unwrap(), "42");
+
+    let route_match = table
+        .resolve(&Route::new(Method::GET, "/users/abc"))
+        .unwrap();
+    assert_eq!(route_match.route.path, "/users/:name<slug>");
+
+    assert!(table
+        .resolve(&Route::new(Method::GET, "/users/Abc_1"))
+        .is_err());
+
+    assert!(table
+        .resolve(&Route::new(
+            Method::GET,
+            "/orders/123e4567-e89b-12d3-a456-426614174000"
+        ))
+        .is_ok());
+    assert!(table
+        .resolve(&Route::new(Method::GET, "/orders/42"))
+        .is_err());
+
+    assert!(RouteTable::from_pairs(&[(Method::GET, "/users/:id<float>")]).is_err());
+}
+
+#[test]
+fn validated_routes() {
+    assert_eq!(
+        Route::try_new(Method::GET, "users").unwrap(),
+        Route::new(Method::GET, "/users")
+    );
+    assert_eq!(
+        Route::try_new(Method::GET, "/users").unwrap().path,
+        "/users"
+    );
+    assert_eq!(Route::try_new(Method::OPTIONS, "*").unwrap().path, "*");
+
+    for path in ["/users\n", "", "/a\tb", "*"] {
+        match Route::try_new(Method::GET, path).unwrap_err() {
+            servidor_http::Error::RouterError(router::RouterError::InvalidRoute(p)) => {
+                assert_eq!(p, path)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn route_table_method_not_allowed() {
+    let table = RouteTable::from_pairs(&[
+        (Method::GET, "/users/:id"),
+        (Method::PUT, "/users/:id"),
+        (Method::GET, "/users/*rest"),
+    ])
+    .unwrap();
+
+    let methods = match table
+        .resolve(&Route::new(Method::DELETE, "/users/1"))
+        .unwrap_err()
+    {
+        servidor_http::Error::RouterError(router::RouterError::MethodNotAllowed(methods)) => {
+            methods
+        }
+        _ => unreachable!(),
+    };
+
+    assert_eq!(methods, vec![Method::GET, Method::PUT, Method::HEAD]);
+
+    match table
+        .resolve(&Route::new(Method::DELETE, "/missing"))
+        .unwrap_err()
+    {
+        servidor_http::Error::RouterError(router::RouterError::RouteNotFound(_)) => (),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn route_table_merge() {
+    let mut api = RouteTable::new();
+    api.handle_route(Route::new(Method::GET, "/users/:id<int>"), hello_handler)
+        .unwrap();
+    api.insert(Route::new(Method::GET, "/files/*path")).unwrap();
+    api.insert(Route::new(Method::GET, "/")).unwrap();
+
+    let mut main = RouteTable::from_pairs(&[(Method::GET, "/about")]).unwrap();
+    main.merge("/api/", api).unwrap();
+
+    let route_match = main
+        .resolve(&Route::new(Method::GET, "/api/users/42"))
+        .unwrap();
+    assert_eq!(route_match.route.path, "/api/users/:id<int>");
+    assert_eq!(route_match.params.get("id").unwrap(), "42");
+    assert!(route_match.handler.is_some());
+
+    let route_match = main
+        .resolve(&Route::new(Method::GET, "/api/files/a/b.txt"))
+        .unwrap();
+    assert_eq!(route_match.params.get("path").unwrap(), "a/b.txt");
+
+    assert!(main.resolve(&Route::new(Method::GET, "/api")).is_ok());
+    assert!(main.resolve(&Route::new(Method::GET, "/about")).is_ok());
+    assert!(main.resolve(&Route::new(Method::GET, "/users/42")).is_err());
+
+    let conflicting =
+        RouteTable::from_pairs(&[(Method::GET, "/health"), (Method::GET, "/users/:user<int>")])
+            .unwrap();
+
+    match main.merge("/api", conflicting).unwrap_err() {
+        servidor_http::Error::RouterError(router::RouterError::RouteConflict(route)) => {
+            assert_eq!(route, Route::new(Method::GET, "/api/users/:user<int>"))
+        }
+        _ => unreachable!(),
+    }
+    assert!(main
+        .resolve(&Route::new(Method::GET, "/api/health"))
+        .is_err());
+}
+
+#[test]
+fn route_table_extension_method() {
+    let mut table = RouteTable::new();
+    table
+        .handle_route(Route::new(Method::from("PROPFIND"), "/docs"), hello_handler)
+        .unwrap();
+
+    let request = Request::try_from("propfind /docs HTTP/1.1\r\n\r\n").unwrap();
+
+    assert!(table.resolve(&request.path).is_ok());
+}